@@ -0,0 +1,165 @@
+//!
+//! The derive macros of [reactive-signals](https://crates.io/crates/reactive-signals).
+//!
+//! Use them through the `derive` feature of reactive-signals, which re-exports them.
+//! See the Scope doc of reactive-signals for how typed data is attached to scopes.
+//!
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, DeriveInput, Ident, LitStr, Path};
+
+/// Generates a scope wrapper that holds the struct as a signal, an `attach_to` function
+/// that creates the wrapper from a scope, and an accessor for the signal named as the
+/// struct but snake-cased.
+///
+/// The `scoped` attribute takes the type of the scope that is wrapped, `Scope` when
+/// omitted, followed by these options:
+/// - `name = "..."`: the name of the generated wrapper, the struct name suffixed
+///   by `Scope` when omitted.
+/// - `eq`: creates an `EqData` signal, which requires [PartialEq].
+/// - `hash`: creates a `HashEqData` signal, which requires [Hash] and [PartialEq].
+#[proc_macro_derive(Scoped, attributes(scoped))]
+pub fn derive_scoped(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match scoped(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+enum Kind {
+    Data,
+    Eq,
+    Hash,
+}
+
+struct Args {
+    inner: Option<Path>,
+    name: Option<Ident>,
+    kind: Kind,
+}
+
+impl Args {
+    fn parse(input: &DeriveInput) -> syn::Result<Self> {
+        let mut args = Self {
+            inner: None,
+            name: None,
+            kind: Kind::Data,
+        };
+        for attr in input.attrs.iter().filter(|a| a.path().is_ident("scoped")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("name") {
+                    let name: LitStr = meta.value()?.parse()?;
+                    args.name = Some(name.parse()?);
+                } else if meta.path.is_ident("eq") {
+                    args.kind = Kind::Eq;
+                } else if meta.path.is_ident("hash") {
+                    args.kind = Kind::Hash;
+                } else if args.inner.is_none() {
+                    args.inner = Some(meta.path);
+                } else {
+                    return Err(meta.error("expected `name = \"...\"`, `eq` or `hash`"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(args)
+    }
+}
+
+fn scoped(input: DeriveInput) -> syn::Result<TokenStream2> {
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "Scoped can't be derived for a generic type",
+        ));
+    }
+    let args = Args::parse(&input)?;
+
+    let vis = &input.vis;
+    let data = &input.ident;
+    let inner = match args.inner {
+        Some(inner) => quote!(#inner),
+        None => quote!(::reactive_signals::Scope),
+    };
+    let wrapper = args.name.unwrap_or_else(|| format_ident!("{}Scope", data));
+    let accessor = Ident::new(&snake_case(&data.to_string()), Span::call_site());
+    let (signal_type, create) = match args.kind {
+        Kind::Data => (quote!(Data), quote!(data)),
+        Kind::Eq => (quote!(EqData), quote!(eq_data)),
+        Kind::Hash => (quote!(HashEqData), quote!(hash_eq_data)),
+    };
+
+    let rt = quote!(::reactive_signals::runtimes::Runtime);
+    let signal =
+        quote!(::reactive_signals::Signal<::reactive_signals::types::#signal_type<#data>, __RT>);
+    let wrapper_doc = format!("A scope with a [{data}] attached, created by `{data}::attach_to`.");
+    let accessor_doc = format!("The signal of the attached [{data}].");
+    let attach_doc = format!("Attaches the value to the scope as a signal, see [{wrapper}].");
+
+    // the generated names start with `__` so that they can't collide with
+    // the name of the struct or the accessor derived from it
+    Ok(quote! {
+        #[doc = #wrapper_doc]
+        #vis struct #wrapper<__RT: #rt> {
+            __scope: #inner<__RT>,
+            __signal: #signal,
+        }
+
+        impl<__RT: #rt> ::core::clone::Clone for #wrapper<__RT> {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl<__RT: #rt> ::core::marker::Copy for #wrapper<__RT> {}
+
+        impl<__RT: #rt> #wrapper<__RT> {
+            #[doc = #accessor_doc]
+            #vis fn #accessor(&self) -> #signal {
+                self.__signal
+            }
+        }
+
+        impl<__RT: #rt> ::core::ops::Deref for #wrapper<__RT> {
+            type Target = #inner<__RT>;
+
+            fn deref(&self) -> &Self::Target {
+                &self.__scope
+            }
+        }
+
+        impl<__RT: #rt> ::core::convert::From<#wrapper<__RT>> for ::reactive_signals::Scope<__RT> {
+            fn from(__wrapper: #wrapper<__RT>) -> Self {
+                __wrapper.__scope.into()
+            }
+        }
+
+        impl #data {
+            #[doc = #attach_doc]
+            #vis fn attach_to<__RT: #rt>(self, __scope: #inner<__RT>) -> #wrapper<__RT> {
+                let __signal = ::reactive_signals::Scope::from(__scope).#create(self);
+                #wrapper { __scope, __signal }
+            }
+        }
+    })
+}
+
+/// Converts a type name such as `HTTPHeader` to `http_header`.
+fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut snake = String::with_capacity(name.len() + 4);
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            if prev.is_lowercase() || prev.is_ascii_digit() || (prev.is_uppercase() && next_lower) {
+                snake.push('_');
+            }
+        }
+        snake.extend(c.to_lowercase());
+    }
+    snake
+}
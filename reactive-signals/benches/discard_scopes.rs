@@ -5,7 +5,7 @@ pub fn discard_scopes_with_signals(c: &mut Criterion) {
         b.iter_batched(
             reactive_signals::tests::profile::create_1000_nested_scopes_each_with_a_signal,
             |(scope, _start, _end)| scope.discard(),
-            BatchSize::SmallInput,
+            BatchSize::PerIteration,
         );
     });
 
@@ -15,7 +15,7 @@ pub fn discard_scopes_with_signals(c: &mut Criterion) {
             b.iter_batched(
                 reactive_signals::tests::profile::create_1000_nested_scopes_each_with_a_signal,
                 |(scope, _, _)| scope.discard(),
-                BatchSize::SmallInput,
+                BatchSize::PerIteration,
             );
         },
     );
@@ -10,7 +10,7 @@ pub fn signal_propagation(c: &mut Criterion) {
                     start_sig.set(2);
                     black_box(end_sig.get())
                 },
-                BatchSize::SmallInput,
+                BatchSize::PerIteration,
             );
         },
     );
@@ -27,7 +27,7 @@ pub fn signal_propagation(c: &mut Criterion) {
                     start_sig.set(2);
                     black_box(end_sig.get())
                 },
-                BatchSize::SmallInput,
+                BatchSize::PerIteration,
             );
         },
     );
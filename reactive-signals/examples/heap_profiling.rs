@@ -15,13 +15,13 @@ fn main() {
     let stats = dhat::HeapStats::get();
     #[cfg(feature = "unsafe-cell")]
     {
-        assert_eq!(stats.max_bytes, 301_044);
-        assert_eq!(stats.max_blocks, 3_005);
+        assert_eq!(stats.max_bytes, 494_604);
+        assert_eq!(stats.max_blocks, 3_007);
     }
     #[cfg(not(feature = "unsafe-cell"))]
     {
-        assert_eq!(stats.max_bytes, 349_284);
-        assert_eq!(stats.max_blocks, 3_005);
+        assert_eq!(stats.max_bytes, 591_260);
+        assert_eq!(stats.max_blocks, 3_007);
     }
     drop(profile);
 
@@ -34,12 +34,12 @@ fn main() {
     let stats = dhat::HeapStats::get();
     #[cfg(feature = "unsafe-cell")]
     {
-        assert_eq!(stats.max_bytes, 32_768);
+        assert_eq!(stats.max_bytes, 90_112);
         assert_eq!(stats.max_blocks, 1);
     }
     #[cfg(not(feature = "unsafe-cell"))]
     {
-        assert_eq!(stats.max_bytes, 40_960);
+        assert_eq!(stats.max_bytes, 114_688);
         assert_eq!(stats.max_blocks, 1);
     }
     drop(profile);
@@ -53,13 +53,13 @@ fn main() {
     let stats = dhat::HeapStats::get();
     #[cfg(feature = "unsafe-cell")]
     {
-        assert_eq!(stats.max_bytes, 73_664);
-        assert_eq!(stats.max_blocks, 1002);
+        assert_eq!(stats.max_bytes, 106_690);
+        assert_eq!(stats.max_blocks, 1003);
     }
     #[cfg(not(feature = "unsafe-cell"))]
     {
-        assert_eq!(stats.max_bytes, 89_888);
-        assert_eq!(stats.max_blocks, 1002);
+        assert_eq!(stats.max_bytes, 131_170);
+        assert_eq!(stats.max_blocks, 1003);
     }
     drop(profile);
 
@@ -72,13 +72,13 @@ fn main() {
     let stats = dhat::HeapStats::get();
     #[cfg(feature = "unsafe-cell")]
     {
-        assert_eq!(stats.max_bytes, 69_664);
-        assert_eq!(stats.max_blocks, 1002);
+        assert_eq!(stats.max_bytes, 102_750);
+        assert_eq!(stats.max_blocks, 1003);
     }
     #[cfg(not(feature = "unsafe-cell"))]
     {
-        assert_eq!(stats.max_bytes, 89_888);
-        assert_eq!(stats.max_blocks, 1002);
+        assert_eq!(stats.max_bytes, 131_218);
+        assert_eq!(stats.max_blocks, 1003);
     }
     drop(profile);
 
@@ -94,13 +94,13 @@ fn main() {
 
     #[cfg(feature = "unsafe-cell")]
     {
-        assert_eq!(stats.max_bytes, 81_768);
-        assert_eq!(stats.max_bytes - stats_no_deps.max_bytes, 12_104);
+        assert_eq!(stats.max_bytes, 122_854);
+        assert_eq!(stats.max_bytes - stats_no_deps.max_bytes, 20_104);
     }
     #[cfg(not(feature = "unsafe-cell"))]
     {
-        assert_eq!(stats.max_bytes, 98_000);
-        assert_eq!(stats.max_bytes - stats_no_deps.max_bytes, 8_112);
+        assert_eq!(stats.max_bytes, 147_334);
+        assert_eq!(stats.max_bytes - stats_no_deps.max_bytes, 16_116);
     }
     drop(profile);
 }
@@ -13,7 +13,14 @@ impl NodeSlotAvailability {
 
     #[inline]
     pub(crate) fn get_available<T>(&mut self, vec: &[Node<T>]) -> Option<NodeId> {
-        get_available(&mut self.0, |i| !(i == 0 || vec[i].is_used())).map(NodeId::from)
+        // the slot can go past the end of the nodes
+        let is_available = |i: usize| {
+            i != 0
+                && vec
+                    .get(i)
+                    .is_some_and(|node| !node.is_used() && !node.is_retired())
+        };
+        get_available(&mut self.0, is_available).map(NodeId::from)
     }
 
     #[inline]
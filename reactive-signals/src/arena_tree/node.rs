@@ -40,12 +40,21 @@ pub struct Node<T> {
     pub parent: Option<NodeId>,
     pub last_child: Option<NodeId>,
     pub prev_sibling: Option<NodeId>,
+    /// Changes every time the node is used for new data, to tell apart
+    /// the ids of the previous uses.
+    pub generation: u32,
 }
 
 impl<T> Node<T> {
     pub fn is_used(&self) -> bool {
         self.parent.is_some()
     }
+
+    /// Whether the node has run out of generations, in which case it
+    /// is never used again.
+    pub fn is_retired(&self) -> bool {
+        self.generation == u32::MAX
+    }
 }
 impl<T> Node<T> {
     pub fn new(data: T) -> Self {
@@ -54,6 +63,7 @@ impl<T> Node<T> {
             parent: None,
             last_child: None,
             prev_sibling: None,
+            generation: 0,
         }
     }
 
@@ -71,6 +81,7 @@ impl<T: Clone> Clone for Node<T> {
             parent: self.parent,
             last_child: self.last_child,
             prev_sibling: self.prev_sibling,
+            generation: self.generation,
         }
     }
 }
@@ -78,5 +89,5 @@ impl<T: Clone> Clone for Node<T> {
 #[test]
 fn test_node_size() {
     let node = Node::<u32>::default();
-    assert_eq!(std::mem::size_of_val(&node), 12);
+    assert_eq!(std::mem::size_of_val(&node), 16);
 }
@@ -55,3 +55,44 @@ fn reuse_ids() {
     "###);
     assert_snapshot!(tree.dump_used(), @"[0] 0, [1] 1, [2] 11, [3] 3, [4] 31");
 }
+
+#[test]
+fn reuse_generations() {
+    use super::Tree;
+
+    let mut tree = Tree::create_and_init(0);
+    let root_gen = tree.generation(tree.root());
+
+    let c1 = tree.add_child(tree.root(), 1);
+    let c1_gen = tree.generation(c1);
+    assert!(tree.is_alive(c1, c1_gen));
+
+    tree.discard(c1, |_| {});
+    assert!(!tree.is_alive(c1, c1_gen));
+    assert!(tree.is_current(c1_gen));
+
+    // the slot is reused with the next generation
+    let c2 = tree.add_child(tree.root(), 2);
+    assert_eq!(c2, c1);
+    assert_eq!(tree.generation(c2), c1_gen + 1);
+    assert!(!tree.is_alive(c1, c1_gen));
+
+    // a slot that has used up its generations is retired
+    tree.nodes[c2.index()].generation = u32::MAX;
+    tree.discard(c2, |_| {});
+    let c3 = tree.add_child(tree.root(), 3);
+    assert_ne!(c3, c2);
+
+    // the ids of the previous use of the tree stay discarded
+    tree.discard_all();
+    tree.init(0);
+    assert!(tree.generation(tree.root()) > root_gen);
+    assert!(!tree.is_alive(tree.root(), root_gen));
+    assert!(!tree.is_current(c1_gen));
+    let c4 = tree.add_child(tree.root(), 4);
+    assert!(!tree.is_alive(c4, c1_gen));
+    assert!(tree.is_current(tree.generation(c4)));
+
+    tree.generation = u32::MAX;
+    assert!(tree.is_exhausted());
+}
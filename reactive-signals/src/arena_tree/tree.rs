@@ -8,6 +8,12 @@ pub struct Tree<T> {
     pub(crate) initialized: bool,
     pub(crate) nodes: Vec<Node<T>>,
     pub(crate) availability: NodeSlotAvailability,
+    /// The highest generation of the nodes. It is kept when the tree is
+    /// discarded so that the ids of a previous use are never valid again.
+    pub(crate) generation: u32,
+    /// The generation of the root when the tree was initialized. The ids
+    /// of the previous uses of the tree are of lower generations.
+    pub(crate) first_generation: u32,
 }
 
 impl<T> Index<NodeId> for Tree<T> {
@@ -42,6 +48,8 @@ impl<T: Clone> Clone for Tree<T> {
             initialized: self.initialized,
             nodes: self.nodes.clone(),
             availability: Default::default(),
+            generation: self.generation,
+            first_generation: self.first_generation,
         }
     }
 }
@@ -56,7 +64,7 @@ impl<T: Default> Tree<T> {
     fn add_node(&mut self) -> NodeId {
         if let Some(id) = self.availability.get_available(&self.nodes) {
             debug_assert!(
-                !self.nodes[id.index()].is_used(),
+                !self.nodes[id.index()].is_used() && !self.nodes[id.index()].is_retired(),
                 "BUG: node {} is already used",
                 id.index()
             );
@@ -78,6 +86,8 @@ impl<T: Default> Tree<T> {
             initialized: false,
             nodes: vec![],
             availability: Default::default(),
+            generation: 0,
+            first_generation: 0,
         }
     }
 
@@ -93,13 +103,45 @@ impl<T: Default> Tree<T> {
             !self.initialized,
             "tree already initialized. did you forget to discard it before reusing it?"
         );
+        assert!(
+            !self.is_exhausted(),
+            "the generations of the tree are exhausted"
+        );
         let root_id = self.availability.init();
 
-        self.nodes.push(Node::new(data));
+        self.first_generation = self.generation + 1;
+        let mut root = Node::new(data);
+        root.generation = self.first_generation;
+        self.generation = self.first_generation;
+        self.nodes.push(root);
         self.initialized = true;
         root_id
     }
 
+    /// Whether all the generations have been used, in which case the tree
+    /// can't be initialized again without making the ids of a previous use
+    /// valid again.
+    pub fn is_exhausted(&self) -> bool {
+        self.generation == u32::MAX
+    }
+
+    /// The generation of the node, which identifies its current use.
+    pub fn generation(&self, id: NodeId) -> u32 {
+        self.nodes[id.index()].generation
+    }
+
+    /// Whether the generation is from after the tree was initialized.
+    pub fn is_current(&self, generation: u32) -> bool {
+        self.initialized && generation >= self.first_generation
+    }
+
+    /// Whether the node is in use and hasn't been reused since `generation`.
+    pub fn is_alive(&self, id: NodeId, generation: u32) -> bool {
+        self.nodes.get(id.index()).is_some_and(|node| {
+            node.generation == generation && (node.is_used() || id == self.root())
+        })
+    }
+
     pub fn root(&self) -> NodeId {
         NodeId::root()
     }
@@ -123,6 +165,10 @@ impl<T: Default> Tree<T> {
         let new_id = self.add_node();
         {
             let node = &mut self.nodes[new_id.index()];
+            // a node that is used for the first time since the tree was
+            // initialized starts at the generation of the root
+            node.generation = (node.generation + 1).max(self.first_generation);
+            self.generation = self.generation.max(node.generation);
             node.data = data;
             node.parent = Some(to);
             node.prev_sibling = prev_sibling;
@@ -5,6 +5,20 @@
 /// - `scope`: mandatory. The [Scope](crate::Scope) to use when creating the [Signal](crate::Signal)
 /// - `clone:`: optional. A space-separated list of data to clone and provide to the function.
 /// - `server` | `client`: optional. Whether the signal should run only on the server or the client.
+/// - `lazy`: optional. The function only runs when the signal is read, and not when it is created
+///   or when the signals it uses change.
+/// - `cmp:`: optional. A function that compares the old value with the new one and returns true
+///   when they are to be considered equal, in which case the subscribers are not notified.
+///   It replaces [PartialEq] for the signal. Ex: `signal!(sc, cmp: |a, b| a == b, value)`.
+/// - `eq` | `hash` | `always`: optional. Selects the kind of signal instead of detecting it, which
+///   is needed in generic code where it can't be seen whether the type implements [PartialEq]
+///   or [Hash](std::hash::Hash). With `eq` and `hash` the subscribers are notified when the value
+///   changes, with `always` whenever it is updated. Ex: `signal!(sc, eq: value)`.
+///   A function is only compared by hash with `hash`, also after `server` or `client`.
+///   Only the hash of its previous value is then compared, so values with the same hash
+///   are taken as equal.
+/// - `effect`: optional. The function doesn't produce a value and runs after all the other signals
+///   affected by a change are up to date. Use it for side effects like logging or updating the DOM.
 /// - `inner`: the data or function the signal handles.
 ///
 /// Examples:
@@ -29,6 +43,30 @@
 /// // Create a signal from data that doesn't implement equality.
 /// // it will always notify the subscribers when it changes.
 /// let no_eq_sig = signal!(sc, MyNoEqData);
+///
+/// // Create a signal that is compared with a custom function. Only
+/// // changes larger than 0.01 notify the subscribers.
+/// let float_sig = signal!(sc, cmp: |a, b| (a - b).abs() < 0.01, 1.0_f64);
+///
+/// // the same is possible with functional signals
+/// let rounded_sig = signal!(sc, cmp: |a, b| a.round() == b.round(), move || float_sig.get() * 10.0);
+/// ```
+///
+/// In generic code the detected kind depends on the bounds of the generic types and
+/// it silently falls back to a signal that always notifies the subscribers. Giving
+/// the kind makes it fail to compile instead.
+///
+/// ```rust
+/// # use reactive_signals::types::*;
+/// use reactive_signals::{Scope, Signal, signal, runtimes::Runtime};
+///
+/// fn eq_signal<T: PartialEq + 'static, RT: Runtime>(sc: Scope<RT>, value: T) -> Signal<EqData<T>, RT> {
+///     signal!(sc, eq: value)
+/// }
+///
+/// fn always_signal<T: 'static, RT: Runtime>(sc: Scope<RT>, value: T) -> Signal<Data<T>, RT> {
+///     signal!(sc, always: value)
+/// }
 /// ```
 ///
 /// # Example of functional reactive signals
@@ -49,6 +87,10 @@
 /// // create a simple functional signal
 /// let func_sig = signal!(sc, move || count_sig.get() + 1);
 ///
+/// // a functional signal can receive the value it produced the previous
+/// // time, or None the first time
+/// let total_sig = signal!(sc, move |total: Option<&i32>| total.unwrap_or(&0) + count_sig.get());
+///
 /// ///////////// the clone argument /////////////
 ///
 /// let counter = Rc::new(RefCell::new(0));
@@ -70,6 +112,19 @@
 ///
 /// // create a signal that only runs on the client
 /// let client_func = signal!(sc, client, move || count_sig.get() + 1);
+///
+/// ///////////// lazy signals /////////////
+///
+/// // create a signal that runs the first time it is read and then
+/// // only when it is read after count_sig has changed
+/// let lazy_func = signal!(sc, lazy, move || count_sig.get() * 1000);
+/// count_sig.set(5);
+/// assert_eq!(lazy_func.get(), 5000);
+///
+/// ///////////// effects /////////////
+///
+/// // runs when created and then after func_sig and lazy_func have been updated
+/// signal!(sc, effect, move || println!("{} {}", func_sig.get(), lazy_func.get()));
 /// ```
 ///
 /// # Example of async functional reactive signals
@@ -99,14 +154,16 @@ macro_rules! signal {
     ($scope:ident, $inner:expr) => {{
         #[allow(unused_imports)]
         use $crate::{EqFuncKind, TrueFuncKind, EqDataKind, TrueDataKind, HashEqDataKind};
-        match ($scope, $inner) {
+        #[allow(unused_imports)]
+        use $crate::{ReducerEqFuncKind, ReducerTrueFuncKind};
+        match ($crate::Scope::from($scope), $inner) {
             tuple => (&&tuple).signal_kind().new(tuple),
         }
     }};
     ($scope:ident, server, $inner:expr) => {{
         #[allow(unused_imports)]
         use $crate::{ServerEqFuncKind, ServerTrueFuncKind};
-        match ($scope, $inner) {
+        match ($crate::Scope::from($scope), $inner) {
             tuple => (&&tuple).server_kind().new(tuple),
         }
     }};
@@ -114,15 +171,22 @@ macro_rules! signal {
         $(let $clone = $clone.clone();)*
         #[allow(unused_imports)]
         use $crate::{ServerEqFuncKind, ServerTrueFuncKind};
-        match ($scope, $inner) {
+        match ($crate::Scope::from($scope), $inner) {
             tuple => (&&tuple).server_kind().new(tuple),
         }
     }};
+    ($scope:ident, server, hash: $inner:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::ServerHashEqFuncKind;
+        match ($crate::Scope::from($scope), $inner) {
+            tuple => tuple.hash_kind().new(tuple),
+        }
+    }};
 
     ($scope:ident, client, $inner:expr) => {{
         #[allow(unused_imports)]
         use $crate::{ClientEqFuncKind, ClientTrueFuncKind};
-        match ($scope, $inner) {
+        match ($crate::Scope::from($scope), $inner) {
             tuple => (&&tuple).client_kind().new(tuple),
         }
     }};
@@ -130,15 +194,88 @@ macro_rules! signal {
         $(let $clone = $clone.clone();)*
         #[allow(unused_imports)]
         use $crate::{ClientEqFuncKind, ClientTrueFuncKind};
-        match ($scope, $inner) {
+        match ($crate::Scope::from($scope), $inner) {
             tuple => (&&tuple).client_kind().new(tuple),
         }
     }};
+    ($scope:ident, client, hash: $inner:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::ClientHashEqFuncKind;
+        match ($crate::Scope::from($scope), $inner) {
+            tuple => tuple.hash_kind().new(tuple),
+        }
+    }};
+    ($scope:ident, lazy, $inner:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::{LazyEqFuncKind, LazyTrueFuncKind};
+        match ($crate::Scope::from($scope), $inner) {
+            tuple => (&&tuple).lazy_kind().new(tuple),
+        }
+    }};
+    ($scope:ident, lazy, clone: $($clone:ident) +, $inner:expr) => {{
+        $(let $clone = $clone.clone();)*
+        #[allow(unused_imports)]
+        use $crate::{LazyEqFuncKind, LazyTrueFuncKind};
+        match ($crate::Scope::from($scope), $inner) {
+            tuple => (&&tuple).lazy_kind().new(tuple),
+        }
+    }};
+    ($scope:ident, effect, $inner:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::EffectKind;
+        match ($crate::Scope::from($scope), $inner) {
+            tuple => (&&tuple).effect_kind().new(tuple),
+        }
+    }};
+    ($scope:ident, effect, clone: $($clone:ident) +, $inner:expr) => {{
+        $(let $clone = $clone.clone();)*
+        #[allow(unused_imports)]
+        use $crate::EffectKind;
+        match ($crate::Scope::from($scope), $inner) {
+            tuple => (&&tuple).effect_kind().new(tuple),
+        }
+    }};
+    ($scope:ident, cmp: $cmp:expr, $inner:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::{CmpDataKind, CmpFuncKind};
+        match ($crate::Scope::from($scope), $inner) {
+            tuple => (&&tuple).cmp_kind().new(tuple, $cmp),
+        }
+    }};
+    ($scope:ident, cmp: $cmp:expr, clone: $($clone:ident) +, $inner:expr) => {{
+        $(let $clone = $clone.clone();)*
+        #[allow(unused_imports)]
+        use $crate::{CmpDataKind, CmpFuncKind};
+        match ($crate::Scope::from($scope), $inner) {
+            tuple => (&&tuple).cmp_kind().new(tuple, $cmp),
+        }
+    }};
+    ($scope:ident, eq: $inner:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::{ExplicitEqDataKind, ExplicitEqFuncKind};
+        match ($crate::Scope::from($scope), $inner) {
+            tuple => (&&tuple).eq_kind().new(tuple),
+        }
+    }};
+    ($scope:ident, hash: $inner:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::{ExplicitHashEqDataKind, ExplicitHashEqFuncKind};
+        match ($crate::Scope::from($scope), $inner) {
+            tuple => (&&tuple).hash_kind().new(tuple),
+        }
+    }};
+    ($scope:ident, always: $inner:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::{ExplicitTrueDataKind, ExplicitTrueFuncKind};
+        match ($crate::Scope::from($scope), $inner) {
+            tuple => (&&tuple).always_kind().new(tuple),
+        }
+    }};
     ($scope:ident, clone: $($clone:ident) +, $data:expr) => {{
         $(let $clone = $clone.clone();)*
         #[allow(unused_imports)]
-        use $crate::{EqFuncKind, TrueFuncKind};
-        match ($scope, $data) {
+        use $crate::{EqFuncKind, TrueFuncKind, ReducerEqFuncKind, ReducerTrueFuncKind};
+        match ($crate::Scope::from($scope), $data) {
             tuple => (&&tuple).signal_kind().new(tuple),
         }
     }};
@@ -180,4 +317,10 @@ fn test() {
     let clnt = signal!(sx, client, move || 1);
 
     assert_eq!(clnt.opt_get(), None);
+
+    let lazy = signal!(sx, lazy, move || 1);
+    assert_eq!(lazy.get(), 1);
+
+    let ne = NonEq;
+    let _sig = signal!(sx, lazy, clone: ne, move || ne.clone());
 }
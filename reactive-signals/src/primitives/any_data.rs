@@ -1,6 +1,6 @@
 use std::any::Any;
 
-use crate::{signals::SignalType, CellType};
+use crate::{signals::SignalType, CellType, SignalError};
 
 pub struct AnyData(pub(crate) Box<CellType<dyn Any>>);
 
@@ -24,6 +24,8 @@ impl AnyData {
         f(val.inner())
     }
 
+    /// Returns whether the value changed, which is only known for a value with
+    /// a hash, together with the result of `f`.
     pub fn update<T, R>(&self, f: impl Fn(&mut T::Inner) -> R) -> (bool, R)
     where
         T: SignalType + 'static,
@@ -34,11 +36,38 @@ impl AnyData {
         let hash_before = val.opt_hash();
         let r = f(val.inner_mut());
         let hash_after = val.opt_hash();
-        let eq = match (hash_before, hash_after) {
-            (Some(h1), Some(h2)) => h1 == h2,
-            _ => false,
+        let changed = match (hash_before, hash_after) {
+            (Some(h1), Some(h2)) => h1 != h2,
+            _ => true,
         };
-        (eq, r)
+        (changed, r)
+    }
+
+    /// `f` returns `changed`, whether it changed the value, which is
+    /// returned as is.
+    pub fn maybe_update<T>(&self, f: impl Fn(&mut T::Inner) -> bool) -> bool
+    where
+        T: SignalType + 'static,
+    {
+        #[allow(unused_mut)]
+        let mut val_any = self.val_mut();
+        let val = (*val_any).downcast_mut::<T>().unwrap();
+        f(val.inner_mut())
+    }
+
+    /// Compares the value after the update with a clone of it from before and
+    /// returns whether it changed, together with the result of `f`.
+    pub fn update_checked<T, R>(&self, f: impl Fn(&mut T::Inner) -> R) -> (bool, R)
+    where
+        T: SignalType + 'static,
+        T::Inner: Clone,
+    {
+        #[allow(unused_mut)]
+        let mut val_any = self.val_mut();
+        let val = (*val_any).downcast_mut::<T>().unwrap();
+        let before = val.inner().clone();
+        let r = f(val.inner_mut());
+        (!val.is_eq(&before), r)
     }
 
     pub fn cloned<T>(&self) -> T::Inner
@@ -61,18 +90,30 @@ impl AnyData {
         *val.inner()
     }
 
+    /// Returns whether the new value differs from the previous one.
     pub fn set<T: SignalType + 'static>(&self, val: T::Inner) -> bool {
         #[allow(unused_mut)]
         let mut val_any = self.val_mut();
         let val_t = (*val_any).downcast_mut::<T>().unwrap();
-        let eq = val_t.is_eq(&val);
-        *val_t.inner_mut() = val;
-        eq
+        let changed = !val_t.is_eq(&val);
+        val_t.set(val);
+        changed
     }
 }
 
 #[cfg(not(feature = "unsafe-cell"))]
 impl AnyData {
+    /// Returns an error if the value is in use in a way that doesn't allow
+    /// it to be read, or written if `write` is true.
+    pub fn check_access(&self, write: bool) -> Result<(), SignalError> {
+        let free = if write {
+            self.0.try_borrow_mut().is_ok()
+        } else {
+            self.0.try_borrow().is_ok()
+        };
+        free.then_some(()).ok_or(SignalError::BorrowConflict)
+    }
+
     #[inline]
     fn val_ref(&self) -> std::cell::Ref<dyn Any> {
         self.0.borrow()
@@ -85,6 +126,11 @@ impl AnyData {
 
 #[cfg(feature = "unsafe-cell")]
 impl AnyData {
+    /// Conflicts can't be detected with an [UnsafeCell](std::cell::UnsafeCell).
+    pub fn check_access(&self, _write: bool) -> Result<(), SignalError> {
+        Ok(())
+    }
+
     #[inline]
     fn val_ref(&self) -> &dyn Any {
         unsafe { &*self.0.get() }
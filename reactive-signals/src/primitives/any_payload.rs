@@ -0,0 +1,57 @@
+use std::any::Any;
+
+use crate::CellType;
+
+/// The payload of an event signal, which is only kept while the
+/// event is propagated. Nothing is allocated until it is emitted.
+#[derive(Default)]
+pub struct AnyPayload(CellType<Option<Box<dyn Any>>>);
+
+impl std::fmt::Debug for AnyPayload {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "AnyPayload")
+    }
+}
+
+impl AnyPayload {
+    pub fn set<T: 'static>(&self, val: T) {
+        *self.val_mut() = Some(Box::new(val));
+    }
+
+    pub fn clear(&self) {
+        *self.val_mut() = None;
+    }
+
+    /// Applies `f` to the payload, which is `None` when the event
+    /// isn't being propagated.
+    pub fn with<T: 'static, R>(&self, f: impl FnOnce(Option<&T>) -> R) -> R {
+        let val = self.val_ref();
+        f(val.as_ref().map(|val| val.downcast_ref::<T>().unwrap()))
+    }
+}
+
+#[cfg(not(feature = "unsafe-cell"))]
+impl AnyPayload {
+    #[inline]
+    fn val_ref(&self) -> std::cell::Ref<'_, Option<Box<dyn Any>>> {
+        self.0.borrow()
+    }
+    #[inline]
+    fn val_mut(&self) -> std::cell::RefMut<'_, Option<Box<dyn Any>>> {
+        self.0.borrow_mut()
+    }
+}
+
+#[cfg(feature = "unsafe-cell")]
+impl AnyPayload {
+    #[inline]
+    fn val_ref(&self) -> &Option<Box<dyn Any>> {
+        unsafe { &*self.0.get() }
+    }
+
+    #[inline]
+    #[allow(clippy::mut_from_ref)]
+    fn val_mut(&self) -> &mut Option<Box<dyn Any>> {
+        unsafe { &mut *self.0.get() }
+    }
+}
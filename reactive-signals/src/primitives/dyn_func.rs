@@ -1,6 +1,9 @@
 use std::any::Any;
 
-use crate::{signals::SignalType, CellType};
+use crate::{
+    signals::{Effect, SignalType},
+    CellType,
+};
 
 use super::AnyData;
 
@@ -23,22 +26,97 @@ impl DynFunc {
         F: Fn() -> T + 'static,
         T: 'static,
     {
-        let val = AnyData::new(W::new(func()));
-        let func = Box::new(move |val: &BoxAnyData| {
-            let new = func();
+        Self::new_with(func, W::new)
+    }
+
+    /// Like [new](Self::new) but uses `wrap` to create the value,
+    /// for types that need more than the value.
+    pub fn new_with<F, T, W: SignalType<Inner = T>>(func: F, wrap: impl FnOnce(T) -> W) -> Self
+    where
+        F: Fn() -> T + 'static,
+        T: 'static,
+    {
+        let value = AnyData::new(wrap(func()));
+        Self {
+            func: Self::wrap::<F, T, W>(func),
+            value,
+        }
+    }
 
-            #[cfg(not(feature = "unsafe-cell"))]
-            let mut old_any = val.borrow_mut();
-            #[cfg(feature = "unsafe-cell")]
-            let old_any: &mut dyn Any = unsafe { &mut *val.get() };
+    /// Creates the function without running it. The value is empty
+    /// until the first run.
+    pub fn lazy<F, T, W: SignalType<Inner = T> + Default>(func: F) -> Self
+    where
+        F: Fn() -> T + 'static,
+        T: 'static,
+    {
+        Self {
+            func: Self::wrap::<F, T, W>(func),
+            value: AnyData::new(W::default()),
+        }
+    }
+
+    /// Runs the effect and creates a function that runs it
+    /// and never reports a change.
+    pub fn effect<F: Fn() + 'static>(effect: F) -> Self {
+        effect();
+        Self {
+            func: Box::new(move |_| {
+                effect();
+                false
+            }),
+            value: AnyData::new(Effect(())),
+        }
+    }
 
-            let old: &mut T = old_any.downcast_mut::<W>().unwrap().inner_mut();
-            *old = new;
-            true
+    /// Creates a function that receives the value it produced the
+    /// previous time, or `None` the first time.
+    pub fn reducer<F, T, W: SignalType<Inner = T>>(func: F) -> Self
+    where
+        F: Fn(Option<&T>) -> T + 'static,
+        T: 'static,
+    {
+        let value = AnyData::new(W::new(func(None)));
+        let func = Box::new(move |val: &BoxAnyData| {
+            let new = {
+                #[cfg(not(feature = "unsafe-cell"))]
+                let old_any = val.borrow();
+                #[cfg(feature = "unsafe-cell")]
+                let old_any: &dyn Any = unsafe { &*val.get() };
+
+                let old = old_any.downcast_ref::<W>().unwrap();
+                func(Some(old.inner()))
+            };
+            Self::replace::<T, W>(val, new)
         });
-        Self { func, value: val }
+        Self { func, value }
+    }
+
+    fn wrap<F, T, W: SignalType<Inner = T>>(func: F) -> Box<dyn Fn(&BoxAnyData) -> bool>
+    where
+        F: Fn() -> T + 'static,
+        T: 'static,
+    {
+        Box::new(move |val: &BoxAnyData| Self::replace::<T, W>(val, func()))
+    }
+
+    /// Replaces the value with `new` and returns true if they differ.
+    fn replace<T, W: SignalType<Inner = T>>(val: &BoxAnyData, new: T) -> bool {
+        #[cfg(not(feature = "unsafe-cell"))]
+        let mut old_any = val.borrow_mut();
+        #[cfg(feature = "unsafe-cell")]
+        let old_any: &mut dyn Any = unsafe { &mut *val.get() };
+
+        let old = old_any.downcast_mut::<W>().unwrap();
+        if old.is_eq(&new) {
+            return false;
+        }
+        old.set(new);
+        true
     }
 
+    /// Runs the function and returns true if the produced value differs from
+    /// the previous one. Values that don't implement [PartialEq] always differ.
     pub fn run(&self) -> bool {
         (self.func)(&self.value.0)
     }
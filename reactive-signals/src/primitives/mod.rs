@@ -1,12 +1,16 @@
 mod any_data;
+mod any_payload;
 mod arr_vec;
 mod dyn_func;
+mod on_unwind;
 mod signal_set;
 mod u15_bool;
 
 pub(crate) use any_data::AnyData;
+pub(crate) use any_payload::AnyPayload;
 pub(crate) use arr_vec::ArrVec;
 pub(crate) use dyn_func::DynFunc;
+pub(crate) use on_unwind::OnUnwind;
 pub(crate) use signal_set::SignalSet;
 pub(crate) use u15_bool::u15Bool;
 
@@ -10,6 +10,10 @@ impl<const N: usize, T: Ord + Eq + Copy> SignalSet<N, T> {
         self.vec_mut().insert(elem);
     }
 
+    pub(crate) fn remove(&self, elem: T) {
+        self.vec_mut().retain(|e| *e != elem);
+    }
+
     pub(crate) fn clear(&self) {
         self.vec_mut().clear()
     }
@@ -1,4 +1,4 @@
-use crate::CellType;
+use crate::{CellType, SignalError};
 
 use super::{Runtime, RuntimeInner, Scope};
 
@@ -32,7 +32,7 @@ pub struct SingleClientRuntime(CellType<RuntimeInner<ClientRuntime>>);
 /// 
 /// See [runtimes](super) for full documentation.
 /// 
-#[derive(Default, Clone, Copy)]
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
 pub struct ClientRuntime;
 
 impl Runtime for ClientRuntime {
@@ -52,6 +52,13 @@ impl Runtime for ClientRuntime {
         RUNTIME.with(|rt| f(&rt.rt_ref()))
     }
 
+    fn try_with_ref<F, T>(&self, f: F) -> Result<T, SignalError>
+    where
+        F: FnOnce(&RuntimeInner<ClientRuntime>) -> Result<T, SignalError>,
+    {
+        RUNTIME.with(|rt| rt.try_rt_ref().and_then(|rt| f(&rt)))
+    }
+
 }
 
 impl  ClientRuntime {
@@ -63,12 +70,16 @@ impl  ClientRuntime {
                 panic!("Runtime is already used. Make sure to not call new_root_scope() more than once on a thread");
             }
             let mut rti = RuntimeInner::new();
+            // the generations go on so that the previous scopes stay discarded
+            rti.scope_tree.generation = data.scope_tree.generation;
             let sx = rti.scope_tree.init(Default::default());
+            let generation = rti.scope_tree.generation(sx);
             *data = rti;
 
             Scope {
                 sx,
                 rt: ClientRuntime,
+                generation,
             }
     
         })
@@ -90,6 +101,11 @@ impl SingleClientRuntime {
         self.0.borrow()
     }
 
+    #[inline]
+    fn try_rt_ref(&self) -> Result<std::cell::Ref<'_, RuntimeInner<ClientRuntime>>, SignalError> {
+        self.0.try_borrow().map_err(|_| SignalError::BorrowConflict)
+    }
+
     #[inline]
     fn rt_mut(&self) -> std::cell::RefMut<RuntimeInner<ClientRuntime>> {
         self.0.borrow_mut()
@@ -103,6 +119,11 @@ impl SingleClientRuntime {
         unsafe { &*self.0.get() }
     }
 
+    #[inline]
+    fn try_rt_ref(&self) -> Result<&RuntimeInner<ClientRuntime>, SignalError> {
+        Ok(self.rt_ref())
+    }
+
     #[inline]
     fn rt_mut(&self) -> &mut RuntimeInner<ClientRuntime> {
         unsafe { &mut *self.0.get() }
@@ -3,23 +3,71 @@ use std::{
     ops::{Index, IndexMut},
 };
 
-use crate::arena_tree::Tree;
+use crate::arena_tree::{FlagVec, NodeId, Tree};
 
-use crate::{signals::SignalId, ScopeInner};
+use crate::{
+    primitives::{OnUnwind, SignalSet},
+    signals::{RunMode, SignalCycle, SignalError, SignalId, SignalInner, SignalState},
+    CellType, ScopeInner,
+};
 
 use super::Runtime;
 
-#[derive(Default)]
+thread_local! {
+    /// The id of the runtime whose signal is running on this thread, if any.
+    static RUNNING_RUNTIME: Cell<Option<u32>> = const { Cell::new(None) };
+    /// The id of the next runtime created on this thread.
+    static NEXT_RUNTIME_ID: Cell<u32> = const { Cell::new(0) };
+}
+
+/// The running signal of a runtime together with the runtime whose signal
+/// is running on the thread, as they were before a signal was set running.
+#[derive(Clone, Copy)]
+pub(crate) struct RunningSignal<RT: Runtime> {
+    signal: Option<SignalId<RT>>,
+    runtime: Option<u32>,
+}
+
 pub struct RuntimeInner<RT: Runtime> {
     pub(crate) scope_tree: Tree<ScopeInner<RT>>,
+    /// Tells the runtimes of a thread apart, whatever their type.
+    id: u32,
     running_signal: Cell<Option<SignalId<RT>>>,
+    /// The signals read by the running signals. Each running signal owns the
+    /// entries pushed after it started, so nested runs share the same vec.
+    tracked: CellType<Vec<SignalId<RT>>>,
+    /// The number of nested batches that are running.
+    batch_depth: Cell<usize>,
+    /// The signals changed during a batch, waiting to be propagated.
+    changed: CellType<Vec<SignalId<RT>>>,
+    /// The signals that are running, the innermost last, together with
+    /// where their reads start in `tracked`.
+    running: CellType<Vec<(SignalId<RT>, usize)>>,
+    /// Whether a propagation is running.
+    in_wave: Cell<bool>,
+    /// The number of nested reads of signal values.
+    read_depth: Cell<usize>,
+    /// Set when a cycle is found, to stop the propagation.
+    stopped: Cell<bool>,
+    /// The last cycle found, until it is taken.
+    cycle: CellType<Option<SignalCycle<RT>>>,
 }
 
 impl<RT: Runtime> RuntimeInner<RT> {
     pub(crate) fn new() -> Self {
+        let id = NEXT_RUNTIME_ID.with(|next| next.replace(next.get().wrapping_add(1)));
         Self {
             scope_tree: Tree::create(),
+            id,
             running_signal: Cell::new(None),
+            tracked: Default::default(),
+            batch_depth: Cell::new(0),
+            changed: Default::default(),
+            running: Default::default(),
+            in_wave: Cell::new(false),
+            read_depth: Cell::new(0),
+            stopped: Cell::new(false),
+            cycle: Default::default(),
         }
     }
 
@@ -31,18 +79,354 @@ impl<RT: Runtime> RuntimeInner<RT> {
         if self.in_use() {
             // also sets the tree to not initialized
             self.scope_tree.discard_all();
+            self.reset_propagation();
+        }
+    }
+
+    /// Resets the state of the propagation, which a panic in a signal
+    /// may have left behind, for the runtime to be reused.
+    fn reset_propagation(&self) {
+        if self.running_signal.take().is_some() {
+            RUNNING_RUNTIME.with(|running| running.set(None));
+        }
+        self.tracked_mut().clear();
+        self.batch_depth.set(0);
+        self.changed_mut().clear();
+        self.running_mut().clear();
+        self.in_wave.set(false);
+        self.read_depth.set(0);
+        self.stopped.set(false);
+        self.cycle_mut().take();
+    }
+
+    /// Forgets the queued changes of the signals of the discarded scopes.
+    pub(crate) fn discard_changes(&self, discarded_scopes: &FlagVec) {
+        self.changed_mut()
+            .retain(|changed| !discarded_scopes.get(changed.sx.as_raw() as usize));
+    }
+
+    /// Tells if the scope `sx` has been discarded since it was of the given
+    /// generation, which means that a handle to it, or to one of its signals,
+    /// is used after the scope was discarded.
+    #[inline]
+    pub(crate) fn try_check_scope(&self, sx: NodeId, generation: u32) -> Result<(), SignalError> {
+        if self.scope_tree.is_alive(sx, generation) {
+            Ok(())
+        } else if self.scope_tree.is_current(generation) {
+            Err(SignalError::ScopeDiscarded)
+        } else {
+            Err(SignalError::RuntimeDiscarded)
         }
     }
 
+    /// Like `try_check_scope` but panics.
+    #[inline]
+    pub(crate) fn check_scope(&self, sx: NodeId, generation: u32) {
+        SignalError::or_panic(self.try_check_scope(sx, generation))
+    }
+
     pub(crate) fn get_running_signal(&self) -> Option<SignalId<RT>> {
         self.running_signal.get()
     }
 
-    pub(crate) fn set_running_signal(&self, signal: Option<SignalId<RT>>) -> Option<SignalId<RT>> {
-        let previous = self.running_signal.take();
+    /// Sets `signal` running, returning what was running to restore it
+    /// with `restore_running_signal`.
+    pub(crate) fn set_running_signal(&self, signal: Option<SignalId<RT>>) -> RunningSignal<RT> {
+        let previous = RunningSignal {
+            signal: self.running_signal.take(),
+            runtime: RUNNING_RUNTIME.with(Cell::get),
+        };
         self.running_signal.set(signal);
+        RUNNING_RUNTIME.with(|running| running.set(signal.map(|_| self.id)));
         previous
     }
+
+    pub(crate) fn restore_running_signal(&self, previous: RunningSignal<RT>) {
+        self.running_signal.set(previous.signal);
+        RUNNING_RUNTIME.with(|running| running.set(previous.runtime));
+    }
+
+    /// Tells if a signal of another runtime is running, which can't
+    /// subscribe to the signals of this one.
+    #[inline]
+    pub(crate) fn try_check_running(&self) -> Result<(), SignalError> {
+        match RUNNING_RUNTIME.with(Cell::get) {
+            Some(id) if id != self.id => Err(SignalError::WrongRuntime),
+            _ => Ok(()),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn in_batch(&self) -> bool {
+        self.batch_depth.get() > 0
+    }
+
+    pub(crate) fn start_batch(&self) {
+        self.batch_depth.set(self.batch_depth.get() + 1);
+    }
+
+    /// Ends a batch that panicked. Its changes are propagated
+    /// together with the next ones.
+    pub(crate) fn unwind_batch(&self) {
+        self.batch_depth
+            .set(self.batch_depth.get().saturating_sub(1));
+    }
+
+    /// Returns the signals changed during the batch once the
+    /// outermost batch ends.
+    pub(crate) fn end_batch(&self) -> Option<Vec<SignalId<RT>>> {
+        let depth = self.batch_depth.get() - 1;
+        self.batch_depth.set(depth);
+        (depth == 0).then(|| self.take_changes())
+    }
+
+    /// Whether signals are running, being propagated or read. Changes made
+    /// meanwhile are queued.
+    #[inline]
+    pub(crate) fn is_busy(&self) -> bool {
+        self.in_wave.get() || self.read_depth.get() > 0 || !self.running_mut().is_empty()
+    }
+
+    /// Runs `f` while reading a signal value.
+    #[inline]
+    pub(crate) fn reading<T>(&self, f: impl FnOnce() -> T) -> T {
+        self.read_depth.set(self.read_depth.get() + 1);
+        let guard = OnUnwind::new(|| self.read_depth.set(self.read_depth.get() - 1));
+        let val = f();
+        guard.disarm();
+        self.read_depth.set(self.read_depth.get() - 1);
+        val
+    }
+
+    pub(crate) fn take_changes(&self) -> Vec<SignalId<RT>> {
+        std::mem::take(&mut *self.changed_mut())
+    }
+
+    /// Queues the change of `sig` for when the batch or propagation ends.
+    pub(crate) fn batch_change(&self, sig: SignalId<RT>) {
+        #[allow(unused_mut)]
+        let mut changed = self.changed_mut();
+        if let Err(idx) = changed.binary_search(&sig) {
+            changed.insert(idx, sig);
+        }
+    }
+
+    /// Whether `id` is running, possibly further up the stack.
+    pub(crate) fn is_running(&self, id: SignalId<RT>) -> bool {
+        self.running_mut().iter().any(|(r, _)| *r == id)
+    }
+
+    /// The outermost running signal that has read `source` during this run.
+    pub(crate) fn running_reader(&self, source: SignalId<RT>) -> Option<SignalId<RT>> {
+        let running = self.running_mut();
+        let tracked = self.tracked_mut();
+        running.iter().enumerate().find_map(|(i, (id, start))| {
+            let end = running.get(i + 1).map_or(tracked.len(), |(_, end)| *end);
+            tracked[*start..end].contains(&source).then_some(*id)
+        })
+    }
+
+    /// Whether the propagation has been stopped because of a cycle.
+    #[inline]
+    pub(crate) fn is_stopped(&self) -> bool {
+        self.stopped.get()
+    }
+
+    pub(crate) fn start_wave(&self) {
+        self.in_wave.set(true);
+        self.stopped.set(false);
+    }
+
+    pub(crate) fn end_wave(&self) {
+        self.in_wave.set(false);
+    }
+
+    /// Reports that the running signal `id` is about to be run again,
+    /// because of a change of `changed` if it is given.
+    ///
+    /// Panics in debug builds, otherwise the propagation is stopped.
+    pub(crate) fn cycle_detected(&self, id: SignalId<RT>, changed: Option<SignalId<RT>>) {
+        let mut path = {
+            let running = self.running_mut();
+            let start = running.iter().position(|(r, _)| *r == id).unwrap_or(0);
+            running[start..].iter().map(|(r, _)| *r).collect::<Vec<_>>()
+        };
+        path.extend(changed);
+        path.push(id);
+        self.report_cycle(SignalCycle::new(&path));
+    }
+
+    /// Reports that the `changed` signals keep being changed by
+    /// the signals that listen to them.
+    pub(crate) fn loop_detected(&self, changed: &[SignalId<RT>]) {
+        self.report_cycle(SignalCycle::new(changed));
+    }
+
+    fn report_cycle(&self, cycle: SignalCycle<RT>) {
+        if cfg!(debug_assertions) {
+            panic!("{cycle}");
+        }
+        self.stopped.set(true);
+        *self.cycle_mut() = Some(cycle);
+    }
+
+    pub(crate) fn take_cycle(&self) -> Option<SignalCycle<RT>> {
+        self.cycle_mut().take()
+    }
+
+    /// Records that the running signal read the `source` signal.
+    #[inline]
+    pub(crate) fn track_source(&self, source: SignalId<RT>) {
+        self.tracked_mut().push(source);
+    }
+
+    /// Whether a running signal has read `id`, which it starts listening
+    /// to once it has run.
+    pub(crate) fn is_tracked(&self, id: SignalId<RT>) -> bool {
+        self.tracked_mut().contains(&id)
+    }
+
+    /// Runs `f` with `id` as the running signal and replaces `sources` with
+    /// the signals it read. The signal starts listening to the sources once
+    /// it has run, and stops listening to the sources it didn't read this time.
+    pub(crate) fn run_tracked<T>(
+        &self,
+        id: SignalId<RT>,
+        sources: &SignalSet<3, SignalId<RT>>,
+        f: impl FnOnce() -> T,
+    ) -> T {
+        let previous = self.set_running_signal(Some(id));
+        let start = self.tracked_mut().len();
+        self.running_mut().push((id, start));
+        let guard = OnUnwind::new(|| {
+            // forget the reads of the run
+            self.running_mut().pop();
+            self.restore_running_signal(previous);
+            self.tracked_mut().truncate(start);
+        });
+        let val = f();
+        guard.disarm();
+        self.running_mut().pop();
+        self.restore_running_signal(previous);
+
+        #[allow(unused_mut)]
+        let mut tracked = self.tracked_mut();
+        let read = &mut tracked[start..];
+        read.sort_unstable();
+
+        sources.retain(|source| {
+            let keep = read.binary_search(source).is_ok();
+            if !keep {
+                self[*source].with_signal(*source, |signal| signal.listeners.remove(id));
+            }
+            keep
+        });
+        tracked.drain(start..).for_each(|source| {
+            self[source].with_signal(source, |signal| signal.listeners.insert(id));
+            sources.insert(source);
+        });
+        val
+    }
+
+    /// Disposes the signal `id`, whose slot is then reused by the
+    /// next signal created in its scope, unless it has run out of versions.
+    pub(crate) fn dispose_signal(&self, id: SignalId<RT>) {
+        if self.is_running(id) {
+            panic!("a signal can't be disposed while it is running");
+        }
+        let retired = self[id].with_signal(id, |signal| {
+            self.unlink_signal(id, signal);
+            signal.dispose();
+            signal.is_retired()
+        });
+        self.changed_mut().retain(|changed| *changed != id);
+        if !retired {
+            self[id].free_signal(id);
+        }
+    }
+
+    /// Freezes the signal `id`, which keeps its value from then on.
+    pub(crate) fn freeze_signal(&self, id: SignalId<RT>) {
+        if self.is_running(id) {
+            panic!("a signal can't be frozen while it is running");
+        }
+        self[id].with_signal(id, |signal| {
+            self.unlink_signal(id, signal);
+            signal.mode.set(RunMode::Frozen);
+            signal.set_state(SignalState::Clean);
+        });
+    }
+
+    /// Removes `id` from the listeners of its sources and from the sources
+    /// of its listeners, so that it is no longer part of any propagation.
+    fn unlink_signal(&self, id: SignalId<RT>, signal: &SignalInner<RT>) {
+        for i in 0..signal.sources.len() {
+            let source = signal.sources.get(i);
+            self[source].with_signal(source, |source| source.listeners.remove(id));
+        }
+        for i in 0..signal.listeners.len() {
+            let listener = signal.listeners.get(i);
+            self[listener].with_signal(listener, |listener| listener.sources.remove(id));
+        }
+        signal.sources.clear();
+        signal.listeners.clear();
+    }
+}
+
+#[cfg(not(feature = "unsafe-cell"))]
+impl<RT: Runtime> RuntimeInner<RT> {
+    #[inline]
+    fn tracked_mut(&self) -> std::cell::RefMut<'_, Vec<SignalId<RT>>> {
+        self.tracked.borrow_mut()
+    }
+
+    #[inline]
+    fn changed_mut(&self) -> std::cell::RefMut<'_, Vec<SignalId<RT>>> {
+        self.changed.borrow_mut()
+    }
+
+    #[inline]
+    fn running_mut(&self) -> std::cell::RefMut<'_, Vec<(SignalId<RT>, usize)>> {
+        self.running.borrow_mut()
+    }
+
+    #[inline]
+    fn cycle_mut(&self) -> std::cell::RefMut<'_, Option<SignalCycle<RT>>> {
+        self.cycle.borrow_mut()
+    }
+}
+
+#[cfg(feature = "unsafe-cell")]
+impl<RT: Runtime> RuntimeInner<RT> {
+    #[inline]
+    #[allow(clippy::mut_from_ref)]
+    fn tracked_mut(&self) -> &mut Vec<SignalId<RT>> {
+        unsafe { &mut *self.tracked.get() }
+    }
+
+    #[inline]
+    #[allow(clippy::mut_from_ref)]
+    fn changed_mut(&self) -> &mut Vec<SignalId<RT>> {
+        unsafe { &mut *self.changed.get() }
+    }
+
+    #[inline]
+    #[allow(clippy::mut_from_ref)]
+    fn running_mut(&self) -> &mut Vec<(SignalId<RT>, usize)> {
+        unsafe { &mut *self.running.get() }
+    }
+
+    #[inline]
+    #[allow(clippy::mut_from_ref)]
+    fn cycle_mut(&self) -> &mut Option<SignalCycle<RT>> {
+        unsafe { &mut *self.cycle.get() }
+    }
+}
+
+impl<RT: Runtime> Default for RuntimeInner<RT> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl<RT: Runtime> Index<SignalId<RT>> for RuntimeInner<RT> {
@@ -21,7 +21,7 @@ mod server;
 mod test_client;
 // mod staticrt;
 
-use crate::Scope;
+use crate::{Scope, SignalError};
 pub use client::ClientRuntime;
 pub(crate) use inner::RuntimeInner;
 pub use server::ServerRuntime;
@@ -29,7 +29,7 @@ pub use test_client::TestClientRuntime;
 // pub use staticrt::{StaticRuntime, StaticRuntimeId};
 
 #[doc(hidden)]
-pub trait Runtime: Default + Copy {
+pub trait Runtime: Default + Copy + Eq {
     const IS_SERVER: bool;
 
     fn with_ref<F, T>(&self, f: F) -> T
@@ -40,6 +40,11 @@ pub trait Runtime: Default + Copy {
     where
         F: FnOnce(&mut RuntimeInner<Self>) -> T;
 
+    /// Like `with_ref` but returns an error if the runtime can't be used.
+    fn try_with_ref<F, T>(&self, f: F) -> Result<T, SignalError>
+    where
+        F: FnOnce(&RuntimeInner<Self>) -> Result<T, SignalError>;
+
     fn discard(&self) {
         self.with_mut(|rt| rt.discard());
     }
@@ -1,6 +1,7 @@
 use std::cell::RefCell;
 
 use super::{Runtime, RuntimeInner, Scope};
+use crate::SignalError;
 
 thread_local! {
   pub static RUNTIME_POOL: TestClientRuntimePool = Default::default();
@@ -29,7 +30,7 @@ thread_local! {
 ///
 /// See [runtimes](super) for full documentation.
 ///
-#[derive(Default, Clone, Copy)]
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
 pub struct TestClientRuntime(u32);
 
 impl TestClientRuntime {
@@ -65,6 +66,20 @@ impl Runtime for TestClientRuntime {
             f(rt)
         })
     }
+
+    fn try_with_ref<F, T>(&self, f: F) -> Result<T, SignalError>
+    where
+        F: FnOnce(&RuntimeInner<TestClientRuntime>) -> Result<T, SignalError>,
+    {
+        RUNTIME_POOL.with(|pool| {
+            let pool = pool
+                .0
+                .try_borrow()
+                .map_err(|_| SignalError::BorrowConflict)?;
+            let rt = pool.get(self.0 as usize).ok_or(SignalError::WrongRuntime)?;
+            f(rt)
+        })
+    }
 }
 
 #[derive(Default)]
@@ -76,11 +91,13 @@ impl TestClientRuntime {
             let mut vec = rt.0.borrow_mut();
 
             for (i, rt) in &mut vec.iter_mut().enumerate() {
-                if !rt.in_use() {
+                // a runtime that has used up its generations isn't reused
+                if !rt.in_use() && !rt.scope_tree.is_exhausted() {
                     let id = rt.scope_tree.init(Default::default());
                     return Scope {
                         rt: TestClientRuntime(i as u32),
                         sx: id,
+                        generation: rt.scope_tree.generation(id),
                     };
                 }
             }
@@ -89,8 +106,13 @@ impl TestClientRuntime {
             let mut rti = RuntimeInner::new();
             rti.scope_tree.init(Default::default());
             let sx = rti.scope_tree.root();
+            let generation = rti.scope_tree.generation(sx);
             vec.push(rti);
-            Scope { rt: id, sx }
+            Scope {
+                rt: id,
+                sx,
+                generation,
+            }
         })
     }
 
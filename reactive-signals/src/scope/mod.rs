@@ -4,6 +4,8 @@ mod tests;
 #[allow(clippy::module_inception)]
 mod scope;
 mod scope_inner;
+mod stored_value;
 
 pub use scope::Scope;
 pub(crate) use scope_inner::ScopeInner;
+pub use stored_value::StoredValue;
@@ -1,15 +1,25 @@
+use std::hash::Hash;
+
 use crate::arena_tree::NodeId;
-use crate::Runtime;
+use crate::primitives::{AnyData, DynFunc, OnUnwind};
+use crate::runtimes::RuntimeInner;
+use crate::signals::{propagate_changes, Event, SignalCycle, SignalMap, SignalVec, Trigger};
+use crate::types::{Data, EqData, EqFunc, Func, HashEqData, HashEqFunc};
+use crate::{Runtime, Signal, StoredValue};
 
 ///
-/// [Signal](crate::Signal)s are created in scopes and can only be deleted by
-/// discarding the scope.
+/// [Signal](crate::Signal)s are created in scopes and are deleted when the scope is
+/// discarded, or one by one with [dispose()](crate::Signal::dispose()).
 ///
 /// Scopes are created in a tree structure, where the root scope is created by one of the [runtimes](crate::runtimes),
 /// and child scopes can be added to any Scope by calling the [new_child()](Self::new_child()) function on a scope.
 ///
 /// When calling a Scope's [discard()](Self::discard()) function, the Scope and it's child scopes are discarded
-/// together with their signals.
+/// together with their signals. Using a discarded Scope, or one of its signals, panics,
+/// even once its id has been reused for a new Scope.
+/// A discarded Scope is told apart from the one that reuses its id by a 32-bit generation,
+/// which is incremented every time the id is reused. An id that has used up its generations
+/// isn't reused anymore.
 ///
 /// Internally, a Scope is really just a u16 index into an arena based tree which contains the
 /// full ScopeInner data (not exposed in the api doc). The Scope implements [Copy] which makes it
@@ -19,80 +29,285 @@ use crate::Runtime;
 ///
 /// ## Typed attached data
 ///
-/// > _**This has not been implemented!** A proof of concept has been done but the exact
-/// > details of how the api will work are not cemented yet._
-///
-///
 /// It is possible to attach data to a Scope and then, in a type-safe and performant manner, access it.
 /// When attached to a Scope the data gets transformed into a Signal which can be retrieved
 /// with a function named as the data struct but snake-cased.
 ///
-/// You can add several nested data values to a scope. The cost of adding one is 2 bytes added
-/// to the scope id which is the vector index of the signal plus the cost of the Signal added to
-/// the ScopeInner.
+/// The `Scoped` derive macro, enabled with the `derive` feature, generates a scope wrapper that
+/// dereferences to the Scope it wraps, so that it can be used wherever that scope can.
+/// The `scoped` attribute gives the type of the wrapped scope, `Scope` when omitted, and
+/// optionally the name of the wrapper with `name = "..."`. An `eq` or `hash` flag creates an
+/// [EqData](crate::types::EqData) or [HashEqData](crate::types::HashEqData) signal instead
+/// of a [Data](crate::types::Data) one.
+///
+/// You can add several nested data values to a scope. The cost of adding one is the size of
+/// a Signal added to the scope wrapper plus the cost of the SignalInner added to the ScopeInner.
+///
+/// ```rust
+/// # use reactive_signals_derive::Scoped;
+/// use reactive_signals::{signal, runtimes::{ClientRuntime, Runtime}, types::*, Scope, Signal};
 ///
-/// ```ignore
-/// // derive using Scoped with the input scope type as argument
 /// // the generated scope wrapper is named MyCounterScope
-/// #[derive(Scoped(Scope), Clone, Copy)]
+/// #[derive(Scoped, PartialEq)]
+/// #[scoped(Scope, eq)]
 /// struct MyCounter(u8);
 ///
-/// // a derive with the name argument as well
-/// #[derive(Scoped(MyCounterScope, name = "BaseScope"), Clone)]
+/// // the wrapper can be named and wrap another wrapper
+/// #[derive(Scoped)]
+/// #[scoped(MyCounterScope, name = "BaseScope")]
 /// struct MyGreeting(String);
 ///
-/// fn some_func<RT: Runtime>(sc: Scope<RT>) {
-///     // create your data
-///     let count = MyCounter(0);
+/// fn some_func<RT: Runtime + 'static>(sc: Scope<RT>) {
+///     // attach the data to the scope (type annotations not necessary)
+///     let sc: MyCounterScope<RT> = MyCounter(2).attach_to(sc);
+///
+///     // the MyCounter instance can be accessed as a signal (type annotations not necessary)
+///     let count_signal: Signal<EqData<MyCounter>, RT> = sc.my_counter();
 ///
-///     // attach it to the scope (type annotations not necessary)
-///     let sc: MyCounterScope<RT: Runtime> = count.attach_to(sc);
-///     
-///     // The MyCount instance can be accessed as a signal (type annotations not necessary)
-///     let count_signal: Signal<EqData<i32>, RT> = sc.my_counter();
-///     
-///     // Create a MyGreeting and attach it to the MyCounterScope
-///     let sc: BaseScope<RT: Runtime> = MyGreeting("hi ".to_string()).attach_to(sc);
+///     // create a MyGreeting and attach it to the MyCounterScope
+///     let sc: BaseScope<RT> = MyGreeting("hi ".to_string()).attach_to(sc);
 ///
 ///     next_func(sc);
 /// }
 ///
-/// // The scope is passed as a typed parameter
-/// fn next_func<RT: Runtime>(sc: BaseScope<RT>) {
+/// // the scope is passed as a typed parameter
+/// fn next_func<RT: Runtime + 'static>(sc: BaseScope<RT>) {
 ///     // the scoped data can be modified
-///     sc.my_greeting().update(|s| *s = *s.trim());
+///     sc.my_greeting().update(|greet| greet.0 = greet.0.trim().to_string());
 ///
 ///     signal!(sc, move || {
-///         sc.my_greeting().with(|greet| println!("{greet} {} times", sc.my_counter().get()))
+///         sc.my_greeting().with(|greet| println!("{} {} times", greet.0, sc.my_counter().with(|c| c.0)))
 ///     });
 /// }
+/// # some_func(ClientRuntime::new_root_scope());
+/// ```
+///
+/// It can't be derived for a generic struct:
+///
+/// ```compile_fail
+/// # use reactive_signals_derive::Scoped;
+/// #[derive(Scoped)]
+/// struct MyValue<T>(T);
 /// ```
 ///
 #[derive(Copy, Clone)]
 pub struct Scope<RT: Runtime> {
     pub(crate) sx: NodeId,
     pub(crate) rt: RT,
+    /// The generation of the node `sx` when the scope was created, which
+    /// tells if the scope has been discarded since.
+    pub(crate) generation: u32,
 }
 
 impl<RT: Runtime> Scope<RT> {
     pub fn new_child(&self) -> Self {
-        self.rt.with_mut(|rt| {
+        self.rt_mut(|rt| {
             let sx = rt.scope_tree.add_child(self.sx, Default::default());
-            Self { sx, rt: self.rt }
+            Self {
+                sx,
+                rt: self.rt,
+                generation: rt.scope_tree.generation(sx),
+            }
         })
     }
 
+    /// Creates a data signal that always notifies the subscribers when it is set.
+    ///
+    /// The builder methods create the same signals as [signal!](crate::signal!) but
+    /// the kind of signal is given by the method instead of being detected, which
+    /// makes them usable in generic code.
+    ///
+    /// ```rust
+    /// # use reactive_signals::{Scope, Signal, types::*, runtimes::{ClientRuntime, Runtime}};
+    /// fn counter<T: PartialEq + 'static, RT: Runtime>(sc: Scope<RT>, start: T) -> Signal<EqData<T>, RT> {
+    ///     sc.eq_data(start)
+    /// }
+    ///
+    /// let sc = ClientRuntime::new_root_scope();
+    /// let count = counter(sc, 1);
+    /// let double = sc.eq_func(move || count.get() * 2);
+    /// assert_eq!(double.get(), 2);
+    /// ```
+    pub fn data<T: 'static>(&self, data: T) -> Signal<Data<T>, RT> {
+        Signal::data(*self, AnyData::new(Data(data)))
+    }
+
+    /// Creates a data signal that only notifies the subscribers when its value changes.
+    pub fn eq_data<T: PartialEq + 'static>(&self, data: T) -> Signal<EqData<T>, RT> {
+        Signal::data(*self, AnyData::new(EqData(data)))
+    }
+
+    /// Creates a data signal that only notifies the subscribers when its value changes.
+    pub fn hash_eq_data<T: Hash + PartialEq + 'static>(
+        &self,
+        data: T,
+    ) -> Signal<HashEqData<T>, RT> {
+        Signal::data(*self, AnyData::new(HashEqData(data)))
+    }
+
+    /// Creates a func signal that notifies the subscribers every time it runs.
+    pub fn func<T: 'static>(&self, func: impl Fn() -> T + 'static) -> Signal<Func<T>, RT> {
+        Signal::func(*self, || DynFunc::new::<_, T, Func<T>>(func))
+    }
+
+    /// Creates a func signal that only notifies the subscribers when the value
+    /// it produces changes.
+    pub fn eq_func<T: PartialEq + 'static>(
+        &self,
+        func: impl Fn() -> T + 'static,
+    ) -> Signal<EqFunc<T>, RT> {
+        Signal::func(*self, || DynFunc::new::<_, T, EqFunc<T>>(func))
+    }
+
+    /// Creates a func signal that only notifies the subscribers when the hash
+    /// of the value it produces changes.
+    pub fn hash_eq_func<T: Hash + 'static>(
+        &self,
+        func: impl Fn() -> T + 'static,
+    ) -> Signal<HashEqFunc<T>, RT> {
+        Signal::func(*self, || DynFunc::new::<_, T, HashEqFunc<T>>(func))
+    }
+
+    /// Creates a [SignalVec] with the given values.
+    pub fn signal_vec<T: Clone + 'static>(&self, values: Vec<T>) -> SignalVec<T, RT>
+    where
+        RT: 'static,
+    {
+        SignalVec::new(*self, values)
+    }
+
+    /// Creates a [SignalMap] with the given entries.
+    pub fn signal_map<K, V>(&self, entries: impl IntoIterator<Item = (K, V)>) -> SignalMap<K, V, RT>
+    where
+        K: Hash + Eq + Clone + 'static,
+        V: 'static,
+        RT: 'static,
+    {
+        SignalMap::new(*self, entries.into_iter().collect())
+    }
+
+    /// Creates a [Trigger], which notifies its subscribers without holding a value.
+    pub fn trigger(&self) -> Trigger<RT> {
+        Trigger::new(*self)
+    }
+
+    /// Creates an [Event], which hands a payload to its subscribers without storing it.
+    pub fn event<T: 'static>(&self) -> Event<T, RT> {
+        Event::new(*self)
+    }
+
+    /// Stores a value that isn't reactive in the scope, where it is kept
+    /// until the scope is discarded. See [StoredValue].
+    pub fn store_value<T: 'static>(&self, value: T) -> StoredValue<T, RT> {
+        StoredValue::new(*self, value)
+    }
+
+    /// Runs `f` and propagates the changes it makes to signals when it returns,
+    /// so that subscribers are only updated once. Batches can be nested, in which
+    /// case the changes are propagated when the outermost batch returns.
+    ///
+    /// Func signals read inside the batch keep their previous value until then.
+    ///
+    /// ```rust
+    /// # use reactive_signals::{signal, runtimes::ClientRuntime};
+    /// # let sc = ClientRuntime::new_root_scope();
+    /// let first = signal!(sc, "John");
+    /// let last = signal!(sc, "Doe");
+    /// let full = signal!(sc, move || format!("{} {}", first.get(), last.get()));
+    ///
+    /// // full is updated once instead of twice
+    /// sc.batch(|| {
+    ///     first.set("Jane");
+    ///     last.set("Roe");
+    /// });
+    /// assert_eq!(full.cloned(), "Jane Roe");
+    /// ```
+    pub fn batch<T>(&self, f: impl FnOnce() -> T) -> T {
+        self.rt_ref(|rt| rt.start_batch());
+        // the scope isn't checked, as `f` may have discarded it
+        let guard = OnUnwind::new(|| self.rt.with_ref(|rt| rt.unwind_batch()));
+        let val = f();
+        guard.disarm();
+        self.rt_ref(|rt| {
+            if let Some(changed) = rt.end_batch() {
+                propagate_changes(rt, &changed);
+            }
+        });
+        val
+    }
+
+    /// Runs `f` without subscribing the running func signal to the signals
+    /// that `f` reads.
+    ///
+    /// ```rust
+    /// # use reactive_signals::{signal, runtimes::ClientRuntime};
+    /// # let sc = ClientRuntime::new_root_scope();
+    /// let count = signal!(sc, 1);
+    /// let step = signal!(sc, 10);
+    ///
+    /// // only re-runs when count changes
+    /// let next = signal!(sc, move || count.get() + sc.untrack(|| step.get()));
+    ///
+    /// step.set(20);
+    /// assert_eq!(next.get(), 11);
+    /// count.set(2);
+    /// assert_eq!(next.get(), 22);
+    /// ```
+    pub fn untrack<T>(&self, f: impl FnOnce() -> T) -> T {
+        let previous = self.rt_ref(|rt| rt.set_running_signal(None));
+        let guard = OnUnwind::new(|| self.rt.with_ref(|rt| rt.restore_running_signal(previous)));
+        let val = f();
+        guard.disarm();
+        self.rt_ref(|rt| rt.restore_running_signal(previous));
+        val
+    }
+
+    /// Takes the last [SignalCycle] found in the runtime, if any.
+    ///
+    /// A cycle is found when a func signal changes a signal it depends on.
+    /// In debug builds it panics, in release builds the propagation of the
+    /// change is stopped and the cycle is kept here.
+    pub fn take_signal_cycle(&self) -> Option<SignalCycle<RT>> {
+        self.rt_ref(|rt| rt.take_cycle())
+    }
+
+    /// Discards the scope, its child scopes and their signals. Using any of them
+    /// afterwards panics.
     pub fn discard(self) {
-        self.rt.with_mut(|rt| {
+        self.rt_mut(|rt| {
             let is_root = rt.scope_tree.root() == self.sx;
             if is_root {
-                self.rt.discard();
+                rt.discard();
             } else {
                 let discarded = rt.scope_tree.discard(self.sx, |s| s.reuse());
+                rt.discard_changes(&discarded);
                 rt.scope_tree
                     .iter_mut_from(rt.scope_tree.root())
                     .for_each(|tree, node| tree[node].remove_scopes(&discarded));
             }
         })
     }
+
+    /// Whether signals are running, being propagated or read, during which
+    /// no scope can be created.
+    pub(crate) fn is_busy(&self) -> bool {
+        self.rt_ref(|rt| rt.is_busy())
+    }
+
+    /// Runs `f` with the runtime.
+    ///
+    /// Panics if the scope has been discarded, as its id may have been reused.
+    pub(crate) fn rt_ref<T>(&self, f: impl FnOnce(&RuntimeInner<RT>) -> T) -> T {
+        self.rt.with_ref(|rt| {
+            rt.check_scope(self.sx, self.generation);
+            f(rt)
+        })
+    }
+
+    fn rt_mut<T>(&self, f: impl FnOnce(&mut RuntimeInner<RT>) -> T) -> T {
+        self.rt.with_mut(|rt| {
+            rt.check_scope(self.sx, self.generation);
+            f(rt)
+        })
+    }
 }
@@ -1,11 +1,15 @@
 use crate::{
-    arena_tree::FlagVec, runtimes::Runtime, scope::Scope, signals::SignalId, signals::SignalInner,
-    CellType,
+    arena_tree::FlagVec, primitives::AnyData, runtimes::Runtime, scope::Scope, signals::SignalId,
+    signals::SignalInner, CellType, SignalError,
 };
 
 #[derive(Debug, Default)]
 pub(crate) struct ScopeInner<RT: Runtime> {
     signals: CellType<Vec<SignalInner<RT>>>,
+    /// The slots of the disposed signals, which are reused by the next signals.
+    free: CellType<Vec<usize>>,
+    /// The values of the [StoredValue](crate::StoredValue)s, which aren't reactive.
+    values: CellType<Vec<AnyData>>,
 }
 
 impl<RT: Runtime> ScopeInner<RT> {
@@ -14,12 +18,40 @@ impl<RT: Runtime> ScopeInner<RT> {
     /// This signal id is not yet valid. There has to be a subsequent
     /// call to `insert_signal` before it is valid
     pub fn next_signal_id(&self, sx: Scope<RT>) -> SignalId<RT> {
-        let idx = self.vec_ref().len();
+        let idx = match self.free_ref().last() {
+            Some(idx) => *idx,
+            None => self.vec_ref().len(),
+        };
         SignalId::new(idx, sx)
     }
 
-    pub fn insert_signal(&self, signal: SignalInner<RT>) {
-        self.vec_mut().push(signal);
+    /// Inserts the signal in the slot of `id` and returns its version,
+    /// which follows the one of the disposed signal the slot is reused from.
+    pub fn insert_signal(&self, id: SignalId<RT>, signal: SignalInner<RT>) -> u16 {
+        #[allow(unused_mut)]
+        let mut signals = self.vec_mut();
+        let idx = id.index();
+        if idx < signals.len() {
+            #[allow(unused_mut)]
+            let mut free = self.free_mut();
+            let pos = free.iter().position(|i| *i == idx).unwrap();
+            free.swap_remove(pos);
+            signal.version.set(signals[idx].version());
+            // drops the disposed signal
+            signals[idx] = signal;
+        } else {
+            signals.push(signal);
+        }
+        signals[idx].version()
+    }
+
+    /// Replaces the signal that reserved the slot of `id`, keeping its version.
+    pub fn replace_signal(&self, id: SignalId<RT>, signal: SignalInner<RT>) {
+        #[allow(unused_mut)]
+        let mut signals = self.vec_mut();
+        let idx = id.index();
+        signal.version.set(signals[idx].version());
+        signals[idx] = signal;
     }
 
     pub fn with_signal<F, T>(&self, id: SignalId<RT>, f: F) -> T
@@ -31,13 +63,63 @@ impl<RT: Runtime> ScopeInner<RT> {
         f(signal)
     }
 
+    /// Like `with_signal` but returns an error when the signals are in use,
+    /// the signal doesn't exist or it has been disposed since it was of
+    /// the given version.
+    pub fn try_with_signal<F, T>(
+        &self,
+        id: SignalId<RT>,
+        version: u16,
+        f: F,
+    ) -> Result<T, SignalError>
+    where
+        F: FnOnce(&SignalInner<RT>) -> Result<T, SignalError>,
+    {
+        let signals = self.try_vec_ref()?;
+        let signal = signals.get(id.index()).ok_or(SignalError::ScopeDiscarded)?;
+        if signal.version() != version {
+            return Err(SignalError::Disposed);
+        }
+        f(signal)
+    }
+
+    /// Frees the slot of the disposed signal `id`.
+    pub fn free_signal(&self, id: SignalId<RT>) {
+        self.free_mut().push(id.index());
+    }
+
+    /// **Warning!**
+    ///
+    /// This value id is not yet valid. There has to be a subsequent
+    /// call to `insert_value` before it is valid
+    pub fn next_value_id(&self, sx: Scope<RT>) -> SignalId<RT> {
+        let idx = self.values_ref().len();
+        SignalId::new(idx, sx)
+    }
+
+    pub fn insert_value(&self, value: AnyData) {
+        self.values_mut().push(value);
+    }
+
+    pub fn with_value<F, T>(&self, id: SignalId<RT>, f: F) -> T
+    where
+        F: FnOnce(&AnyData) -> T,
+    {
+        let values = self.values_ref();
+        let value = values.get(id.index()).unwrap();
+        f(value)
+    }
+
     pub(crate) fn remove_scopes(&mut self, discarded_scopes: &FlagVec) {
         #[allow(unused_mut)]
         let mut signals = self.vec_mut();
         signals.iter_mut().for_each(|signal| {
             signal
                 .listeners
-                .retain(|s| !discarded_scopes.get(s.sx.as_raw() as usize))
+                .retain(|s| !discarded_scopes.get(s.sx.as_raw() as usize));
+            signal
+                .sources
+                .retain(|s| !discarded_scopes.get(s.sx.as_raw() as usize));
         });
     }
 
@@ -46,6 +128,8 @@ impl<RT: Runtime> ScopeInner<RT> {
         let mut signals = self.vec_mut();
         signals.iter_mut().for_each(|signal| signal.reuse());
         signals.clear();
+        self.free_mut().clear();
+        self.values_mut().clear();
     }
 }
 
@@ -56,10 +140,37 @@ impl<RT: Runtime> ScopeInner<RT> {
         self.signals.borrow()
     }
 
+    #[inline]
+    fn try_vec_ref(&self) -> Result<std::cell::Ref<'_, Vec<SignalInner<RT>>>, SignalError> {
+        self.signals
+            .try_borrow()
+            .map_err(|_| SignalError::BorrowConflict)
+    }
+
     #[inline]
     fn vec_mut(&self) -> std::cell::RefMut<Vec<SignalInner<RT>>> {
         self.signals.borrow_mut()
     }
+
+    #[inline]
+    fn free_ref(&self) -> std::cell::Ref<'_, Vec<usize>> {
+        self.free.borrow()
+    }
+
+    #[inline]
+    fn free_mut(&self) -> std::cell::RefMut<'_, Vec<usize>> {
+        self.free.borrow_mut()
+    }
+
+    #[inline]
+    fn values_ref(&self) -> std::cell::Ref<'_, Vec<AnyData>> {
+        self.values.borrow()
+    }
+
+    #[inline]
+    fn values_mut(&self) -> std::cell::RefMut<'_, Vec<AnyData>> {
+        self.values.borrow_mut()
+    }
 }
 #[cfg(feature = "unsafe-cell")]
 impl<RT: Runtime> ScopeInner<RT> {
@@ -68,8 +179,35 @@ impl<RT: Runtime> ScopeInner<RT> {
         unsafe { &*self.signals.get() }
     }
 
+    #[inline]
+    fn try_vec_ref(&self) -> Result<&Vec<SignalInner<RT>>, SignalError> {
+        Ok(self.vec_ref())
+    }
+
     #[inline]
     fn vec_mut(&self) -> &mut Vec<SignalInner<RT>> {
         unsafe { &mut *self.signals.get() }
     }
+
+    #[inline]
+    fn free_ref(&self) -> &Vec<usize> {
+        unsafe { &*self.free.get() }
+    }
+
+    #[inline]
+    #[allow(clippy::mut_from_ref)]
+    fn free_mut(&self) -> &mut Vec<usize> {
+        unsafe { &mut *self.free.get() }
+    }
+
+    #[inline]
+    fn values_ref(&self) -> &Vec<AnyData> {
+        unsafe { &*self.values.get() }
+    }
+
+    #[inline]
+    #[allow(clippy::mut_from_ref)]
+    fn values_mut(&self) -> &mut Vec<AnyData> {
+        unsafe { &mut *self.values.get() }
+    }
 }
@@ -0,0 +1,92 @@
+use std::marker::PhantomData;
+
+use crate::{primitives::AnyData, runtimes::Runtime, signals::SignalId, types::Data, Scope};
+
+/// A value that isn't reactive, such as a cache or a handle, kept as long as
+/// the [Scope] it was stored in and reachable from closures, as it is [Copy].
+/// It is created with [Scope::store_value].
+///
+/// Reading or changing it doesn't subscribe or notify anyone.
+///
+/// ```rust
+/// # use reactive_signals::{signal, runtimes::ClientRuntime};
+/// # use std::collections::HashMap;
+/// # let sc = ClientRuntime::new_root_scope();
+/// let cache = sc.store_value(HashMap::new());
+/// let id = signal!(sc, 1);
+///
+/// let name = signal!(sc, move || {
+///     let id = id.get();
+///     cache.update(|cache| cache.entry(id).or_insert_with(|| format!("user {id}")).clone())
+/// });
+///
+/// id.set(2);
+/// assert_eq!(name.cloned(), "user 2");
+/// assert_eq!(cache.with(|cache| cache.len()), 2);
+/// ```
+pub struct StoredValue<T: 'static, RT: Runtime> {
+    id: SignalId<RT>,
+    generation: u32,
+    ty: PhantomData<T>,
+}
+
+impl<T: 'static, RT: Runtime> Clone for StoredValue<T, RT> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: 'static, RT: Runtime> Copy for StoredValue<T, RT> {}
+
+impl<T: 'static, RT: Runtime> StoredValue<T, RT> {
+    pub(crate) fn new(sx: Scope<RT>, value: T) -> Self {
+        let id = sx.rt_ref(|rt| {
+            let scope = &rt.scope_tree[sx.sx];
+            let id = scope.next_value_id(sx);
+            scope.insert_value(AnyData::new(Data(value)));
+            id
+        });
+        Self {
+            id,
+            generation: sx.generation,
+            ty: PhantomData,
+        }
+    }
+
+    /// Applies a function to the value and returns whatever that function returns.
+    pub fn with<R>(&self, f: impl Fn(&T) -> R) -> R {
+        self.id.checked_rt_ref(self.generation, |rt| {
+            rt[self.id].with_value(self.id, |value| value.with::<Data<T>, R>(f))
+        })
+    }
+
+    /// Applies a function to the value to mutate it in place and returns
+    /// whatever that function returns.
+    pub fn update<R>(&self, f: impl Fn(&mut T) -> R) -> R {
+        self.id.checked_rt_ref(self.generation, |rt| {
+            rt[self.id].with_value(self.id, |value| value.update::<Data<T>, R>(f).1)
+        })
+    }
+
+    pub fn set(&self, val: T) {
+        self.id.checked_rt_ref(self.generation, |rt| {
+            rt[self.id].with_value(self.id, |value| value.set::<Data<T>>(val))
+        });
+    }
+
+    /// Get a clone of the value.
+    pub fn cloned(&self) -> T
+    where
+        T: Clone,
+    {
+        self.with(T::clone)
+    }
+
+    /// Get a copy of the value.
+    pub fn get(&self) -> T
+    where
+        T: Copy,
+    {
+        self.with(|value| *value)
+    }
+}
@@ -1,10 +1,6 @@
-use std::{marker::PhantomData, ops::Deref, rc::Rc};
+use std::rc::Rc;
 
-use crate::{
-    runtimes::{Runtime, ServerRuntime},
-    signal,
-    tests::StringStore,
-};
+use crate::{runtimes::ServerRuntime, signal, tests::StringStore};
 
 #[test]
 fn test_scopes_deep() {
@@ -50,115 +46,145 @@ fn test_scopes_discard() {
     assert_eq!(output.values(), "val: 5, val: 4");
 }
 
-#[derive(Copy, Clone)]
-pub struct ScopeId<RT: Runtime> {
-    pub(crate) _sx: usize,
-    pub(crate) _rt: RT,
-}
+#[test]
+fn test_scope_batch() {
+    let sc = ServerRuntime::new_root_scope();
 
-impl<RT: Runtime> Scope<RT> for ScopeId<RT> {
-    fn get_id(&self) -> usize {
-        self._sx
-    }
-}
-pub trait Scope<RT: Runtime> {
-    fn get_id(&self) -> usize;
-}
+    let a_sig = signal!(sc, 1);
+    let b_sig = signal!(sc, 2);
 
-pub struct ScopeWrap1<I, RT>
-where
-    I: Scope<RT>,
-    RT: Runtime,
-{
-    inner: I,
-    pos: usize,
-    rt: PhantomData<RT>,
+    let output = Rc::new(StringStore::new());
+    let _sum_sig = signal!(sc, clone: output, move || output
+        .push(format!("sum: {}", a_sig.get() + b_sig.get())));
+
+    let sum = sc.batch(|| {
+        a_sig.set(10);
+        b_sig.set(20);
+        sc.batch(|| a_sig.set(100));
+        // the nested batch doesn't propagate
+        assert_eq!(output.values(), "sum: 3");
+        a_sig.get() + b_sig.get()
+    });
+
+    assert_eq!(sum, 120);
+    assert_eq!(output.values(), "sum: 3, sum: 120");
+
+    // nothing changed
+    sc.batch(|| a_sig.set(100));
+    assert_eq!(output.values(), "sum: 3, sum: 120");
 }
 
-impl<I, RT> ScopeWrap1<I, RT>
-where
-    I: Scope<RT>,
-    RT: Runtime,
-{
-    fn get_pos(&self) -> usize {
-        self.pos
-    }
-}
+#[test]
+fn test_scope_discard_in_batch() {
+    let root = ServerRuntime::new_root_scope();
+    let child = root.new_child();
 
-impl<I: Scope<RT>, RT: Runtime> Scope<RT> for ScopeWrap1<I, RT> {
-    fn get_id(&self) -> usize {
-        self.inner.get_id()
-    }
+    let a_sig = signal!(child, 1);
+    let b_sig = signal!(root, 1);
+    let output = Rc::new(StringStore::new());
+    let _sum_sig = signal!(root, clone: output, move || output
+        .push(format!("b: {}", b_sig.get())));
+
+    root.batch(|| {
+        a_sig.set(2);
+        b_sig.set(2);
+        // the change of a is forgotten with its scope
+        child.discard();
+    });
+    assert_eq!(output.values(), "b: 1, b: 2");
 }
 
-impl<I: Scope<RT>, RT: Runtime> Deref for ScopeWrap1<I, RT> {
-    type Target = I;
-
-    fn deref(&self) -> &Self::Target {
-        &self.inner
-    }
+#[test]
+fn test_scope_batch_panic() {
+    use std::panic::catch_unwind;
+
+    let sc = ServerRuntime::new_root_scope();
+    let num_sig = signal!(sc, 1);
+    let ten_sig = signal!(sc, move || num_sig.get() * 10);
+
+    let res = catch_unwind(|| {
+        sc.batch(|| {
+            num_sig.set(2);
+            panic!("in batch");
+        })
+    });
+    assert!(res.is_err());
+    // the change is propagated with the next ones
+    sc.batch(|| ());
+    assert_eq!(ten_sig.get(), 20);
+
+    assert!(catch_unwind(|| sc.untrack(|| panic!("untracked"))).is_err());
+    let _untracked_sig = signal!(sc, move || sc.untrack(|| num_sig.get()));
+    num_sig.set(3);
+    assert_eq!(ten_sig.get(), 30);
+
+    assert!(catch_unwind(|| sc.batch(|| panic!("in batch"))).is_err());
+    sc.discard();
+
+    // the runtime is reset for the next root scope
+    let sc = ServerRuntime::new_root_scope();
+    let num_sig = signal!(sc, 1);
+    let ten_sig = signal!(sc, move || num_sig.get() * 10);
+    num_sig.set(2);
+    assert_eq!(ten_sig.get(), 20);
 }
 
-pub struct ScopeWrap2<I, RT>
-where
-    I: Scope<RT>,
-    RT: Runtime,
-{
-    inner: I,
-    some_data: usize,
-    rt: PhantomData<RT>,
-}
+#[test]
+fn test_scope_stored_value() {
+    let root = ServerRuntime::new_root_scope();
+    let sc = root.new_child();
 
-impl<I: Scope<RT>, RT: Runtime> Scope<RT> for ScopeWrap2<I, RT> {
-    fn get_id(&self) -> usize {
-        self.inner.get_id()
-    }
-}
+    let handle = Rc::new("handle");
+    let stored = sc.store_value(handle.clone());
+    let count = sc.store_value(1);
 
-impl<I, RT> ScopeWrap2<I, RT>
-where
-    I: Scope<RT>,
-    RT: Runtime,
-{
-    #[allow(dead_code)]
-    fn get_some_data(&self) -> usize {
-        self.some_data
-    }
-}
-impl<I: Scope<RT>, RT: Runtime> Deref for ScopeWrap2<I, RT> {
-    type Target = I;
+    let output = Rc::new(StringStore::new());
+    let num_sig = signal!(sc, 5);
+    let _str_sig = signal!(sc, clone: output, move || {
+        count.update(|c| *c += 1);
+        output.push(format!("{} {}", stored.with(|h| **h), num_sig.get()))
+    });
 
-    fn deref(&self) -> &Self::Target {
-        &self.inner
-    }
-}
+    // changing a stored value doesn't notify anyone
+    count.set(10);
+    num_sig.set(4);
+    assert_eq!(output.values(), "handle 5, handle 4");
+    assert_eq!(count.get(), 11);
+    assert_eq!(Rc::strong_count(&handle), 2);
 
-fn get_scope_id<RT: Runtime>() -> ScopeId<RT> {
-    unimplemented!()
+    // the values are dropped with the scope
+    sc.discard();
+    assert_eq!(Rc::strong_count(&handle), 1);
 }
 
-fn some_func<RT: Runtime, SC: Scope<RT>>(_sc: SC) {}
+#[test]
+#[should_panic(expected = "has been discarded")]
+fn test_scope_discarded_signal() {
+    let root = ServerRuntime::new_root_scope();
+
+    let sc = root.new_child();
+    let old_sig = signal!(sc, 5);
+    sc.discard();
 
-#[allow(dead_code)]
-fn chained_deref() {
-    let scope = get_scope_id::<ServerRuntime>();
+    // reuses the node of the discarded scope
+    let sc = root.new_child();
+    let new_sig = signal!(sc, 6);
+    assert_eq!(new_sig.get(), 6);
 
-    let wrap1 = ScopeWrap1 {
-        inner: scope,
-        pos: 2,
-        rt: PhantomData,
-    };
+    old_sig.get();
+}
 
-    wrap1.get_id();
+#[test]
+#[should_panic(expected = "has been discarded")]
+fn test_scope_discarded_runtime() {
+    let root = ServerRuntime::new_root_scope();
+    let old_sig = signal!(root, 5);
+    root.discard();
 
-    let wrap2 = ScopeWrap2 {
-        inner: wrap1,
-        some_data: 4,
-        rt: PhantomData,
-    };
+    // reuses the discarded runtime
+    let root = ServerRuntime::new_root_scope();
+    let new_sig = signal!(root, 6);
+    assert_eq!(new_sig.get(), 6);
 
-    wrap2.get_id();
-    wrap2.get_pos();
-    wrap2.get_some_data();
-    some_func(wrap2);
+    old_sig.set(7);
 }
@@ -0,0 +1,206 @@
+use std::{collections::HashMap, hash::Hash};
+
+use crate::{
+    primitives::{u15Bool, AnyData},
+    runtimes::Runtime,
+    scope::Scope,
+    StoredValue,
+};
+
+use super::{Data, Signal};
+
+/// The number of signals put in a scope, which is as many as a scope can hold.
+const SIGNALS_PER_SCOPE: usize = u15Bool::MAX as usize;
+
+/// The number of signals from which the ones nobody listens to are disposed.
+const SWEEP_MIN: usize = 64;
+
+#[cfg(test)]
+thread_local! {
+    static TEST_SIGNALS_PER_SCOPE: std::cell::Cell<usize> =
+        const { std::cell::Cell::new(SIGNALS_PER_SCOPE) };
+}
+
+/// Lowers the number of signals put in a scope by the key signals created
+/// afterwards on the thread, for the tests to fill scopes with a few signals.
+#[cfg(test)]
+pub(crate) fn set_signals_per_scope(count: usize) {
+    TEST_SIGNALS_PER_SCOPE.with(|per_scope| per_scope.set(count));
+}
+
+fn signals_per_scope() -> usize {
+    #[cfg(test)]
+    return TEST_SIGNALS_PER_SCOPE.with(|per_scope| per_scope.get());
+    #[cfg(not(test))]
+    SIGNALS_PER_SCOPE
+}
+
+struct KeyState<K, RT: Runtime> {
+    signals: HashMap<K, Signal<Data<()>, RT>>,
+    /// The scopes of the signals and the number of signals in each.
+    scopes: Vec<(Scope<RT>, usize)>,
+    /// The number of signals put in a scope.
+    per_scope: usize,
+    /// The number of signals at which the next sweep is done.
+    next_sweep: usize,
+}
+
+impl<K: Hash + Eq, RT: Runtime> KeyState<K, RT> {
+    /// Reserves a place for a signal in one of the scopes, if there's room.
+    fn reserve(&mut self) -> Option<Scope<RT>> {
+        let (sx, count) = self
+            .scopes
+            .iter_mut()
+            .find(|(_, count)| *count < self.per_scope)?;
+        *count += 1;
+        Some(*sx)
+    }
+
+    fn is_full(&self) -> bool {
+        self.scopes
+            .iter()
+            .all(|(_, count)| *count >= self.per_scope)
+    }
+
+    /// Disposes the signal of `key` if nobody listens to it.
+    fn release(&mut self, key: &K) {
+        let Some(sig) = self.signals.get(key).copied() else {
+            return;
+        };
+        if sig.is_unused() {
+            self.signals.remove(key);
+            self.dispose(sig);
+        }
+    }
+
+    /// Disposes the signals that nobody listens to, once there are
+    /// twice as many signals as after the last sweep.
+    fn sweep(&mut self) {
+        if self.signals.len() < self.next_sweep {
+            return;
+        }
+        let mut unused = Vec::new();
+        self.signals.retain(|_, sig| {
+            let keep = !sig.is_unused();
+            if !keep {
+                unused.push(*sig);
+            }
+            keep
+        });
+        unused.into_iter().for_each(|sig| self.dispose(sig));
+        self.next_sweep = (self.signals.len() * 2).max(self.sweep_min());
+    }
+
+    /// Sweeps before a scope is full, when it holds fewer signals than `SWEEP_MIN`.
+    fn sweep_min(&self) -> usize {
+        SWEEP_MIN.min(self.per_scope)
+    }
+
+    fn dispose(&mut self, sig: Signal<Data<()>, RT>) {
+        let sx = sig.scope().sx;
+        if let Some((_, count)) = self.scopes.iter_mut().find(|(scope, _)| scope.sx == sx) {
+            *count -= 1;
+        }
+        sig.dispose();
+    }
+}
+
+/// The signals of the keys of a collection, such as the indexes of a
+/// [SignalVec](super::SignalVec), for the readers of a single key.
+///
+/// They are created when a key is first read and disposed once nobody listens
+/// to them. They are spread over child scopes, as a scope can only hold so many
+/// signals. A scope can't be created while signals are running, so when there's
+/// no room in the scopes meanwhile, no signal is created and the reader has to
+/// subscribe to the whole collection.
+pub(crate) struct KeySignals<K: 'static, RT: Runtime + 'static> {
+    state: StoredValue<KeyState<K, RT>, RT>,
+    /// The scope the scopes of the signals are created in.
+    sx: Scope<RT>,
+}
+
+impl<K: 'static, RT: Runtime + 'static> Clone for KeySignals<K, RT> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K: 'static, RT: Runtime + 'static> Copy for KeySignals<K, RT> {}
+
+impl<K, RT> KeySignals<K, RT>
+where
+    K: Hash + Eq + Clone + 'static,
+    RT: Runtime + 'static,
+{
+    /// Creates the pool, whose scopes are children of `sx`. The first one is
+    /// created right away, unless signals are running.
+    pub(crate) fn new(sx: Scope<RT>) -> Self {
+        let mut state: KeyState<K, RT> = KeyState {
+            signals: HashMap::new(),
+            scopes: Vec::new(),
+            per_scope: signals_per_scope(),
+            next_sweep: 0,
+        };
+        state.next_sweep = state.sweep_min();
+        let keys = Self {
+            state: sx.store_value(state),
+            sx,
+        };
+        keys.add_scope();
+        keys
+    }
+
+    /// Adds a scope if they are all full and no signal is running.
+    ///
+    /// The key signals are created while other signals are read, which
+    /// isn't possible in the scope of the signals that are running.
+    fn add_scope(&self) {
+        if !self.sx.is_busy() && self.state.with(|state| state.is_full()) {
+            let sx = self.sx.new_child();
+            self.state.update(|state| state.scopes.push((sx, 0)));
+        }
+    }
+
+    /// The number of signals.
+    #[cfg(test)]
+    pub(crate) fn len(&self) -> usize {
+        self.state.with(|state| state.signals.len())
+    }
+
+    /// The signal of `key`, if it has been read.
+    pub(crate) fn get(&self, key: &K) -> Option<Signal<Data<()>, RT>> {
+        self.state.with(|state| state.signals.get(key).copied())
+    }
+
+    /// The signals of the `keys` that have been read.
+    pub(crate) fn get_all(
+        &self,
+        keys: impl Iterator<Item = K> + Clone,
+    ) -> Vec<Signal<Data<()>, RT>> {
+        self.state.with(|state| {
+            keys.clone()
+                .filter_map(|key| state.signals.get(&key).copied())
+                .collect()
+        })
+    }
+
+    /// The signal of `key`, which is created if it doesn't exist. It is `None`
+    /// when there's no room in the scopes and no scope can be added for now.
+    pub(crate) fn get_or_create(&self, key: &K) -> Option<Signal<Data<()>, RT>> {
+        if let Some(sig) = self.get(key) {
+            return Some(sig);
+        }
+        self.state.update(|state| state.sweep());
+        self.add_scope();
+        let sx = self.state.update(|state| state.reserve())?;
+        let sig = Signal::data(sx, AnyData::new(Data(())));
+        self.state
+            .update(|state| state.signals.insert(key.clone(), sig));
+        Some(sig)
+    }
+
+    /// Disposes the signal of `key` if nobody listens to it.
+    pub(crate) fn release(&self, key: &K) {
+        self.state.update(|state| state.release(key));
+    }
+}
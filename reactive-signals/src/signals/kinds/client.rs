@@ -1,7 +1,9 @@
+use std::hash::Hash;
+
 use crate::primitives::DynFunc;
 use crate::{runtimes::Runtime, Scope};
 
-use crate::signals::{ClientEqFunc, ClientFunc, Signal};
+use crate::signals::{ClientEqFunc, ClientFunc, ClientHashEqFunc, Signal};
 
 pub trait ClientEqFuncKind {
     #[inline]
@@ -10,8 +12,8 @@ pub trait ClientEqFuncKind {
     }
 }
 
-// Does not require any autoref if called as (&error).datakind().
-impl<F, T, RT: Runtime> ClientEqFuncKind for (Scope<RT>, F)
+// Matched first when called as (&&tuple).client_kind().
+impl<F, T, RT: Runtime> ClientEqFuncKind for &(Scope<RT>, F)
 where
     F: Fn() -> T + 'static,
     T: PartialEq + 'static,
@@ -25,14 +27,44 @@ pub trait ClientTrueFuncKind {
     }
 }
 
-// Requires one extra autoref to call! Lower priority than EqKind.
-impl<F, T, RT: Runtime> ClientTrueFuncKind for &(Scope<RT>, F)
+// Requires one extra autoref to call! Lower priority than ClientEqFuncKind.
+impl<F, T, RT: Runtime> ClientTrueFuncKind for &&(Scope<RT>, F)
 where
     F: Fn() -> T + 'static,
     T: 'static,
 {
 }
 
+pub struct ClientHashEqFuncSignal;
+
+impl ClientHashEqFuncSignal {
+    #[inline]
+    pub fn new<F, T, RT: Runtime>(self, tuple: (Scope<RT>, F)) -> Signal<ClientHashEqFunc<T>, RT>
+    where
+        F: Fn() -> T + 'static,
+        T: Hash + 'static,
+    {
+        let (sx, func) = tuple;
+        Signal::func(sx, || DynFunc::new::<F, T, ClientHashEqFunc<T>>(func))
+    }
+}
+
+// Only selected by the `client, hash:` arguments of `signal!`, as comparing the
+// hashes is opt-in.
+pub trait ClientHashEqFuncKind {
+    #[inline]
+    fn hash_kind(&self) -> ClientHashEqFuncSignal {
+        ClientHashEqFuncSignal
+    }
+}
+
+impl<F, T, RT: Runtime> ClientHashEqFuncKind for (Scope<RT>, F)
+where
+    F: Fn() -> T + 'static,
+    T: Hash + 'static,
+{
+}
+
 pub struct ClientEqFuncSignal;
 
 impl ClientEqFuncSignal {
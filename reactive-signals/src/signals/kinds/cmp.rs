@@ -0,0 +1,61 @@
+use crate::primitives::{AnyData, DynFunc};
+use crate::{runtimes::Runtime, Scope};
+
+use crate::signals::{CmpData, CmpFunc, Signal};
+
+pub trait CmpFuncKind {
+    #[inline]
+    fn cmp_kind(&self) -> CmpFuncSignal {
+        CmpFuncSignal
+    }
+}
+
+// Matched first when called as (&&tuple).cmp_kind().
+impl<F, T, RT: Runtime> CmpFuncKind for &(Scope<RT>, F)
+where
+    F: Fn() -> T + 'static,
+    T: 'static,
+{
+}
+
+pub trait CmpDataKind {
+    #[inline]
+    fn cmp_kind(&self) -> CmpDataSignal {
+        CmpDataSignal
+    }
+}
+
+// Requires a deref to call! Lower priority than CmpFuncKind, as a
+// function is data as well.
+impl<T, RT: Runtime> CmpDataKind for (Scope<RT>, T) where T: 'static {}
+
+pub struct CmpFuncSignal;
+
+impl CmpFuncSignal {
+    #[inline]
+    pub fn new<F, T, E, RT: Runtime>(self, tuple: (Scope<RT>, F), cmp: E) -> Signal<CmpFunc<T>, RT>
+    where
+        F: Fn() -> T + 'static,
+        T: 'static,
+        E: Fn(&T, &T) -> bool + 'static,
+    {
+        let (sx, func) = tuple;
+        Signal::func(sx, || {
+            DynFunc::new_with(func, |value| CmpFunc(value, Box::new(cmp)))
+        })
+    }
+}
+
+pub struct CmpDataSignal;
+
+impl CmpDataSignal {
+    #[inline]
+    pub fn new<T, E, RT: Runtime>(self, tuple: (Scope<RT>, T), cmp: E) -> Signal<CmpData<T>, RT>
+    where
+        T: 'static,
+        E: Fn(&T, &T) -> bool + 'static,
+    {
+        let (sx, data) = tuple;
+        Signal::data(sx, AnyData::new(CmpData(data, Box::new(cmp))))
+    }
+}
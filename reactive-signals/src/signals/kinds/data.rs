@@ -24,7 +24,7 @@ pub trait EqDataKind {
     }
 }
 
-// Does not require any autoref if called as (&error).datakind().
+// Matched first when called as (&&tuple).signal_kind().
 impl<T, RT: Runtime> EqDataKind for &(Scope<RT>, T) where T: PartialEq + 'static {}
 
 pub trait TrueDataKind {
@@ -34,8 +34,9 @@ pub trait TrueDataKind {
     }
 }
 
-// Requires one extra autoref to call! Lower priority than EqKind.
-impl<T, RT: Runtime> TrueDataKind for &&(Scope<RT>, T) where T: 'static {}
+// Requires a deref to call! Lower priority than the func kinds, as a
+// function is data as well.
+impl<T, RT: Runtime> TrueDataKind for (Scope<RT>, T) where T: 'static {}
 
 pub struct HashEqSignal;
 
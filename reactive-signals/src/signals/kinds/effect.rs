@@ -0,0 +1,26 @@
+use crate::primitives::DynFunc;
+use crate::{runtimes::Runtime, Scope};
+
+use crate::signals::{Effect, Signal};
+
+pub trait EffectKind {
+    #[inline]
+    fn effect_kind(&self) -> EffectSignal {
+        EffectSignal
+    }
+}
+
+impl<F, RT: Runtime> EffectKind for &(Scope<RT>, F) where F: Fn() + 'static {}
+
+pub struct EffectSignal;
+
+impl EffectSignal {
+    #[inline]
+    pub fn new<F, RT: Runtime>(self, tuple: (Scope<RT>, F)) -> Signal<Effect, RT>
+    where
+        F: Fn() + 'static,
+    {
+        let (sx, func) = tuple;
+        Signal::effect(sx, || DynFunc::effect(func))
+    }
+}
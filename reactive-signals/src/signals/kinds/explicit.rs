@@ -0,0 +1,86 @@
+use std::hash::Hash;
+
+use crate::{runtimes::Runtime, Scope};
+
+use super::data::{EqSignal, HashEqSignal, TrueSignal};
+use super::func::{EqFuncSignal, HashEqFuncSignal, TrueFunc};
+
+// The kinds below are selected by the `eq:`, `hash:` and `always:` arguments
+// of `signal!`. Only the choice between a func and data is left to the autoref
+// specialization. In generic code, where it can't see that a T implements
+// PartialEq, it then fails to compile instead of falling back to `Data<T>`.
+
+pub trait ExplicitEqFuncKind {
+    #[inline]
+    fn eq_kind(&self) -> EqFuncSignal {
+        EqFuncSignal
+    }
+}
+
+// Matched first when called as (&&tuple).eq_kind().
+impl<F, T, RT: Runtime> ExplicitEqFuncKind for &(Scope<RT>, F)
+where
+    F: Fn() -> T + 'static,
+    T: PartialEq + 'static,
+{
+}
+
+pub trait ExplicitEqDataKind {
+    #[inline]
+    fn eq_kind(&self) -> EqSignal {
+        EqSignal
+    }
+}
+
+// Requires a deref to call! Lower priority than ExplicitEqFuncKind.
+impl<T, RT: Runtime> ExplicitEqDataKind for (Scope<RT>, T) where T: PartialEq + 'static {}
+
+pub trait ExplicitHashEqFuncKind {
+    #[inline]
+    fn hash_kind(&self) -> HashEqFuncSignal {
+        HashEqFuncSignal
+    }
+}
+
+// Matched first when called as (&&tuple).hash_kind().
+impl<F, T, RT: Runtime> ExplicitHashEqFuncKind for &(Scope<RT>, F)
+where
+    F: Fn() -> T + 'static,
+    T: Hash + 'static,
+{
+}
+
+pub trait ExplicitHashEqDataKind {
+    #[inline]
+    fn hash_kind(&self) -> HashEqSignal {
+        HashEqSignal
+    }
+}
+
+// Requires a deref to call! Lower priority than ExplicitHashEqFuncKind.
+impl<T, RT: Runtime> ExplicitHashEqDataKind for (Scope<RT>, T) where T: Hash + PartialEq + 'static {}
+
+pub trait ExplicitTrueFuncKind {
+    #[inline]
+    fn always_kind(&self) -> TrueFunc {
+        TrueFunc
+    }
+}
+
+// Matched first when called as (&&tuple).always_kind().
+impl<F, T, RT: Runtime> ExplicitTrueFuncKind for &(Scope<RT>, F)
+where
+    F: Fn() -> T + 'static,
+    T: 'static,
+{
+}
+
+pub trait ExplicitTrueDataKind {
+    #[inline]
+    fn always_kind(&self) -> TrueSignal {
+        TrueSignal
+    }
+}
+
+// Requires a deref to call! Lower priority than ExplicitTrueFuncKind.
+impl<T, RT: Runtime> ExplicitTrueDataKind for (Scope<RT>, T) where T: 'static {}
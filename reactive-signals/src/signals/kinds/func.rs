@@ -1,7 +1,9 @@
+use std::hash::Hash;
+
 use crate::{
     primitives::DynFunc,
     runtimes::Runtime,
-    signals::{EqFunc, Func},
+    signals::{EqFunc, Func, HashEqFunc},
     Scope, Signal,
 };
 
@@ -12,8 +14,8 @@ pub trait EqFuncKind {
     }
 }
 
-// Does not require any autoref if called as (&error).datakind().
-impl<F, T, RT: Runtime> EqFuncKind for (Scope<RT>, F)
+// Matched first when called as (&&tuple).signal_kind().
+impl<F, T, RT: Runtime> EqFuncKind for &(Scope<RT>, F)
 where
     F: Fn() -> T + 'static,
     T: PartialEq + 'static,
@@ -27,14 +29,28 @@ pub trait TrueFuncKind {
     }
 }
 
-// Requires one extra autoref to call! Lower priority than EqKind.
-impl<F, T, RT: Runtime> TrueFuncKind for &(Scope<RT>, F)
+// Requires one extra autoref to call! Lower priority than EqFuncKind.
+impl<F, T, RT: Runtime> TrueFuncKind for &&(Scope<RT>, F)
 where
     F: Fn() -> T + 'static,
     T: 'static,
 {
 }
 
+pub struct HashEqFuncSignal;
+
+impl HashEqFuncSignal {
+    #[inline]
+    pub fn new<F, T, RT: Runtime>(self, tuple: (Scope<RT>, F)) -> Signal<HashEqFunc<T>, RT>
+    where
+        F: Fn() -> T + 'static,
+        T: Hash + 'static,
+    {
+        let (sx, func) = tuple;
+        Signal::func(sx, || DynFunc::new::<F, T, HashEqFunc<T>>(func))
+    }
+}
+
 pub struct EqFuncSignal;
 
 impl EqFuncSignal {
@@ -0,0 +1,62 @@
+use crate::primitives::DynFunc;
+use crate::{runtimes::Runtime, Scope};
+
+use crate::signals::{LazyEqFunc, LazyFunc, Signal};
+
+pub trait LazyEqFuncKind {
+    #[inline]
+    fn lazy_kind(&self) -> LazyEqFuncSignal {
+        LazyEqFuncSignal
+    }
+}
+
+// Matched first when called as (&&tuple).lazy_kind().
+impl<F, T, RT: Runtime> LazyEqFuncKind for &(Scope<RT>, F)
+where
+    F: Fn() -> T + 'static,
+    T: PartialEq + 'static,
+{
+}
+
+pub trait LazyTrueFuncKind {
+    #[inline]
+    fn lazy_kind(&self) -> LazyTrueFuncSignal {
+        LazyTrueFuncSignal
+    }
+}
+
+// Requires one extra autoref to call! Lower priority than LazyEqFuncKind.
+impl<F, T, RT: Runtime> LazyTrueFuncKind for &&(Scope<RT>, F)
+where
+    F: Fn() -> T + 'static,
+    T: 'static,
+{
+}
+
+pub struct LazyEqFuncSignal;
+
+impl LazyEqFuncSignal {
+    #[inline]
+    pub fn new<F, T, RT: Runtime>(self, tuple: (Scope<RT>, F)) -> Signal<LazyEqFunc<T>, RT>
+    where
+        F: Fn() -> T + 'static,
+        T: PartialEq + 'static,
+    {
+        let (sx, func) = tuple;
+        Signal::lazy_func(sx, DynFunc::lazy::<F, T, LazyEqFunc<T>>(func))
+    }
+}
+
+pub struct LazyTrueFuncSignal;
+
+impl LazyTrueFuncSignal {
+    #[inline]
+    pub fn new<F, T, RT: Runtime>(self, tuple: (Scope<RT>, F)) -> Signal<LazyFunc<T>, RT>
+    where
+        F: Fn() -> T + 'static,
+        T: 'static,
+    {
+        let (sx, func) = tuple;
+        Signal::lazy_func(sx, DynFunc::lazy::<F, T, LazyFunc<T>>(func))
+    }
+}
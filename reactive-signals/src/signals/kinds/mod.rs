@@ -1,16 +1,34 @@
 #![doc(hidden)]
 
 mod client;
+mod cmp;
 mod data;
+mod effect;
+mod explicit;
 mod func;
+mod lazy;
+mod reducer;
 mod server;
 
 // https://github.com/dtolnay/case-studies/tree/master/autoref-specialization
 
 pub use func::{EqFuncKind, TrueFuncKind};
 
+pub use reducer::{ReducerEqFuncKind, ReducerTrueFuncKind};
+
 pub use data::{EqDataKind, HashEqDataKind, TrueDataKind};
 
-pub use server::{ServerEqFuncKind, ServerTrueFuncKind};
+pub use server::{ServerEqFuncKind, ServerHashEqFuncKind, ServerTrueFuncKind};
+
+pub use client::{ClientEqFuncKind, ClientHashEqFuncKind, ClientTrueFuncKind};
+
+pub use lazy::{LazyEqFuncKind, LazyTrueFuncKind};
+
+pub use effect::EffectKind;
+
+pub use cmp::{CmpDataKind, CmpFuncKind};
 
-pub use client::{ClientEqFuncKind, ClientTrueFuncKind};
+pub use explicit::{
+    ExplicitEqDataKind, ExplicitEqFuncKind, ExplicitHashEqDataKind, ExplicitHashEqFuncKind,
+    ExplicitTrueDataKind, ExplicitTrueFuncKind,
+};
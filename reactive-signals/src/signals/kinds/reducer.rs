@@ -0,0 +1,66 @@
+use crate::{
+    primitives::DynFunc,
+    runtimes::Runtime,
+    signals::{EqFunc, Func},
+    Scope, Signal,
+};
+
+// Generic over T, as T isn't constrained when it is only in the arguments
+// of F, which are higher-ranked because of the reference.
+pub trait ReducerEqFuncKind<T> {
+    #[inline]
+    fn signal_kind(&self) -> ReducerEqFuncSignal {
+        ReducerEqFuncSignal
+    }
+}
+
+// Matched first when called as (&&tuple).signal_kind().
+impl<F, T, RT: Runtime> ReducerEqFuncKind<T> for &(Scope<RT>, F)
+where
+    F: Fn(Option<&T>) -> T + 'static,
+    T: PartialEq + 'static,
+{
+}
+
+pub trait ReducerTrueFuncKind<T> {
+    #[inline]
+    fn signal_kind(&self) -> ReducerTrueFuncSignal {
+        ReducerTrueFuncSignal
+    }
+}
+
+// Requires one extra autoref to call! Lower priority than ReducerEqFuncKind.
+impl<F, T, RT: Runtime> ReducerTrueFuncKind<T> for &&(Scope<RT>, F)
+where
+    F: Fn(Option<&T>) -> T + 'static,
+    T: 'static,
+{
+}
+
+pub struct ReducerEqFuncSignal;
+
+impl ReducerEqFuncSignal {
+    #[inline]
+    pub fn new<F, T, RT: Runtime>(self, tuple: (Scope<RT>, F)) -> Signal<EqFunc<T>, RT>
+    where
+        F: Fn(Option<&T>) -> T + 'static,
+        T: PartialEq + 'static,
+    {
+        let (sx, func) = tuple;
+        Signal::func(sx, || DynFunc::reducer::<F, T, EqFunc<T>>(func))
+    }
+}
+
+pub struct ReducerTrueFuncSignal;
+
+impl ReducerTrueFuncSignal {
+    #[inline]
+    pub fn new<F, T, RT: Runtime>(self, tuple: (Scope<RT>, F)) -> Signal<Func<T>, RT>
+    where
+        F: Fn(Option<&T>) -> T + 'static,
+        T: 'static,
+    {
+        let (sx, func) = tuple;
+        Signal::func(sx, || DynFunc::reducer::<F, T, Func<T>>(func))
+    }
+}
@@ -1,7 +1,9 @@
+use std::hash::Hash;
+
 use crate::primitives::DynFunc;
 use crate::{runtimes::Runtime, Scope};
 
-use crate::signals::{ServerEqFunc, ServerFunc, Signal};
+use crate::signals::{ServerEqFunc, ServerFunc, ServerHashEqFunc, Signal};
 
 pub trait ServerEqFuncKind {
     #[inline]
@@ -10,8 +12,8 @@ pub trait ServerEqFuncKind {
     }
 }
 
-// Does not require any autoref if called as (&error).datakind().
-impl<F, T, RT: Runtime> ServerEqFuncKind for (Scope<RT>, F)
+// Matched first when called as (&&tuple).server_kind().
+impl<F, T, RT: Runtime> ServerEqFuncKind for &(Scope<RT>, F)
 where
     F: Fn() -> T + 'static,
     T: PartialEq + 'static,
@@ -25,14 +27,44 @@ pub trait ServerTrueFuncKind {
     }
 }
 
-// Requires one extra autoref to call! Lower priority than EqKind.
-impl<F, T, RT: Runtime> ServerTrueFuncKind for &(Scope<RT>, F)
+// Requires one extra autoref to call! Lower priority than ServerEqFuncKind.
+impl<F, T, RT: Runtime> ServerTrueFuncKind for &&(Scope<RT>, F)
 where
     F: Fn() -> T + 'static,
     T: 'static,
 {
 }
 
+pub struct ServerHashEqFuncSignal;
+
+impl ServerHashEqFuncSignal {
+    #[inline]
+    pub fn new<F, T, RT: Runtime>(self, tuple: (Scope<RT>, F)) -> Signal<ServerHashEqFunc<T>, RT>
+    where
+        F: Fn() -> T + 'static,
+        T: Hash + 'static,
+    {
+        let (sx, func) = tuple;
+        Signal::func(sx, || DynFunc::new::<F, T, ServerHashEqFunc<T>>(func))
+    }
+}
+
+// Only selected by the `server, hash:` arguments of `signal!`, as comparing the
+// hashes is opt-in.
+pub trait ServerHashEqFuncKind {
+    #[inline]
+    fn hash_kind(&self) -> ServerHashEqFuncSignal {
+        ServerHashEqFuncSignal
+    }
+}
+
+impl<F, T, RT: Runtime> ServerHashEqFuncKind for (Scope<RT>, F)
+where
+    F: Fn() -> T + 'static,
+    T: Hash + 'static,
+{
+}
+
 pub struct ServerEqFuncSignal;
 
 impl ServerEqFuncSignal {
@@ -1,20 +1,41 @@
 #[cfg(test)]
 mod tests;
 
+mod key_signals;
 pub(crate) mod kinds;
 mod signal_accessors;
+mod signal_cycle;
+mod signal_derived;
+mod signal_error;
+mod signal_event;
 mod signal_id;
 mod signal_inner;
+mod signal_lifecycle;
+mod signal_map;
 mod signal_new;
+mod signal_selector;
+mod signal_slice;
+mod signal_split;
+mod signal_vec;
 pub mod types;
 mod updater;
 
 use std::marker::PhantomData;
 
-use crate::runtimes::Runtime;
+use crate::{runtimes::Runtime, Scope};
+pub(crate) use key_signals::KeySignals;
+pub use signal_cycle::SignalCycle;
+pub use signal_error::SignalError;
+pub use signal_event::{Event, Trigger};
 pub(crate) use signal_id::SignalId;
-pub(crate) use signal_inner::{SignalInner, SignalValue};
+pub(crate) use signal_inner::{RunMode, SignalInner, SignalState, SignalValue};
+pub use signal_map::SignalMap;
+pub use signal_selector::Selector;
+pub use signal_slice::SignalSlice;
+pub use signal_split::{ReadSignal, WriteSignal};
+pub use signal_vec::{SignalVec, VecDiff};
 pub(crate) use types::*;
+pub(crate) use updater::propagate_changes;
 
 #[doc(hidden)]
 pub use kinds::*;
@@ -24,24 +45,58 @@ pub use kinds::*;
 ///
 /// When it is a function, the function automatically subscribes to all the other
 /// signals it is using and automatically re-runs when any of those signals change.
+/// The subscriptions are renewed on every run, so a signal that is no longer
+/// used, for instance because it is behind an `if`, doesn't trigger a re-run.
+///
+/// A function can also receive the value it produced the previous time, as in
+/// `signal!(sx, move |total: Option<&i32>| total.copied().unwrap_or(0) + count.get())`,
+/// which is `None` the first time. See also [scan](Signal::scan).
 ///
 /// If the value implements [PartialEq] then the subscribers are notified only if
 /// the value changed.
 ///
 /// A [Signal] is created in a reactive [Scope](crate::Scope) using the [signal!](crate::signal!) macro.
-/// It can only be deleted by discarding that [Scope](crate::Scope).
+/// It is deleted when that [Scope](crate::Scope) is discarded, or on its own with
+/// [dispose](Signal::dispose).
+/// It can also be made a constant with [freeze](Signal::freeze), and a func signal can be
+/// re-run with [invalidate](Signal::invalidate).
 ///
 /// ## Accessors
 ///
 /// Only data signals can be manually changed. Func signals that only runs on `server` or `client`
 /// always return optional values which are some only when runninig on their side.
 ///
-/// | Value implements | Data signal          | Func signal | Func signal with<br>`server` or `client` |
-/// | ---              | --                   | ---         | ---                                      |
-/// | -                | .set, .update, .with | .with       | .opt_with                                |
-/// | [Clone]          | .cloned              | .cloned     | .opt_cloned                              |
-/// | [Copy]           | .get                 | .get        | .opt_get                                 |
+/// | Value implements | Data signal                           | Func signal | Func signal with<br>`server` or `client` |
+/// | ---              | --                                    | ---         | ---                                      |
+/// | -                | .set, .update, .maybe_update, .with   | .with       | .opt_with                                |
+/// | [Clone]          | .cloned, .update_checked              | .cloned     | .opt_cloned                              |
+/// | [Copy]           | .get                                  | .get        | .opt_get                                 |
+///
+/// Reading a signal inside a func signal subscribes the func signal to it. To read it
+/// without subscribing, use `.with_untracked` or `.peek` (for [Copy] values), or wrap
+/// the reads in [Scope::untrack](crate::Scope::untrack). Conversely, `.track` subscribes
+/// to a signal without reading it.
+///
+/// The accessors panic when the signal can't be used, for instance because its scope has
+/// been discarded. The `.try_get`, `.try_with`, `.try_set` and `.try_update` accessors
+/// return a [SignalError] instead.
+///
+/// To hand out a data signal without allowing it to be changed, or the other
+/// way around, use [split](Signal::split), [read_only](Signal::read_only) or
+/// [write_only](Signal::write_only).
+///
+/// A part of a data signal, such as a field, can be read and changed on its own
+/// with a [slice](Signal::slice), which only notifies when that part changes.
+///
+/// To tell subscribers that something happened, rather than that a value changed,
+/// use a [Trigger] or an [Event], which don't store a value.
 ///
+/// Side effects are best done in effects, created with `signal!(sx, effect, ...)`, which
+/// run after all the func signals affected by a change are up to date.
+///
+/// A func signal can change data signals. The changes are propagated once all the
+/// signals affected by the current change are up to date, and the propagation
+/// stops with a [SignalCycle] if the changes keep causing new changes.
 ///
 /// ## Example
 ///
@@ -85,12 +140,11 @@ pub use kinds::*;
 /// name.update(|t| *t = "fig");
 /// assert_eq!(text.cloned(), "1 fig");
 ///
-/// // 1 kiwi is repated because when changing count, is_plural changes as well
-/// // triggering a second update of the text. This will be detected in
-/// // future versions and only notified once.
+/// // changing count changes is_plural as well, but text is
+/// // only updated once, after both of them are up to date.
 /// assert_eq!(
 ///     history.with(|h| h.join(", ")),
-///     "5 kiwis, 1 kiwi, 1 kiwi, 1 fig"
+///     "5 kiwis, 1 kiwi, 1 fig"
 /// );
 ///
 /// with_signal_arg(count);
@@ -104,6 +158,10 @@ pub use kinds::*;
 /// ```
 pub struct Signal<T: SignalType, RT: Runtime> {
     id: SignalId<RT>,
+    /// The generation of the signal's scope, see [Scope](crate::Scope).
+    generation: u32,
+    /// The version of the signal's slot, which changes when it is disposed.
+    version: u16,
     ty: PhantomData<T>,
 }
 
@@ -111,6 +169,8 @@ impl<T: SignalType, RT: Runtime> Clone for Signal<T, RT> {
     fn clone(&self) -> Self {
         Self {
             id: self.id,
+            generation: self.generation,
+            version: self.version,
             ty: self.ty,
         }
     }
@@ -118,6 +178,17 @@ impl<T: SignalType, RT: Runtime> Clone for Signal<T, RT> {
 
 impl<T: SignalType, RT: Runtime> Copy for Signal<T, RT> {}
 
+impl<T: SignalType, RT: Runtime> Signal<T, RT> {
+    /// The scope the signal was created in.
+    pub(crate) fn scope(&self) -> Scope<RT> {
+        Scope {
+            sx: self.id.sx,
+            rt: self.id.rt,
+            generation: self.generation,
+        }
+    }
+}
+
 #[test]
 fn test_example() {
     use crate::{runtimes::ClientRuntime, signal};
@@ -159,11 +230,7 @@ fn test_example() {
     name.update(|t| *t = "fig");
     assert_eq!(text.cloned(), "1 fig");
 
-    // 1 kiwi is repated because when changing count, is_plural changes as well
-    // triggering a second update of the text. This will be detected in
-    // future versions and only notified once.
-    assert_eq!(
-        history.with(|h| h.join(", ")),
-        "5 kiwis, 1 kiwi, 1 kiwi, 1 fig"
-    );
+    // changing count changes is_plural as well, but text is
+    // only updated once, after both of them are up to date.
+    assert_eq!(history.with(|h| h.join(", ")), "5 kiwis, 1 kiwi, 1 fig");
 }
@@ -1,8 +1,9 @@
-use crate::runtimes::Runtime;
+use crate::{primitives::AnyData, runtimes::Runtime};
 
 use super::{
-    updater::propagate_change, Modifiable, OptReadable, Readable, Signal, SignalId, SignalInner,
-    SignalType,
+    updater::{flush_changes, propagate_change, update_if_necessary},
+    Modifiable, OptReadable, Readable, RunMode, Signal, SignalError, SignalId, SignalInner,
+    SignalState, SignalType,
 };
 
 impl<T, RT> Signal<T, RT>
@@ -13,20 +14,37 @@ where
     /// Set the signal's value and notifies subscribers
     /// if the value changed when it implements `PartialEq`
     /// otherwise it always notifies.
+    ///
+    /// When called from a func signal, or while reading a signal, the value is
+    /// set right away but the subscribers are notified once the running
+    /// propagation or read is done.
     pub fn set(&self, val: T::Inner) {
-        self.id.rt_ref(|rt| {
-            let is_equal = rt[self.id].with_signal(self.id, |sig| sig.value().set::<T>(val));
-            if !is_equal {
+        SignalError::or_panic(self.try_set(val))
+    }
+
+    /// Like [set](Self::set) but returns an error instead of panicking
+    /// when the signal can't be used.
+    pub fn try_set(&self, val: T::Inner) -> Result<(), SignalError> {
+        self.id.try_rt_ref(self.generation, |rt| {
+            let changed = rt[self.id].try_with_signal(self.id, self.version, |sig| {
+                sig.check_writable()?;
+                sig.value().check_access(true)?;
+                Ok(sig.value().set::<T>(val))
+            })?;
+            if changed {
                 propagate_change(rt, self.id);
             }
-        });
+            Ok(())
+        })
     }
 
     /// Applies a function to the current value to mutate it in place and returns
     /// whatever that function returns.
     ///
     /// Subscribers are notified if the value changed when it implements [PartialEq]
-    /// otherwise it always notifies.
+    /// and [Hash](std::hash::Hash), otherwise it always notifies. Use
+    /// [update_checked](Self::update_checked) or [maybe_update](Self::maybe_update)
+    /// to avoid notifying when nothing changed.
     ///
     /// **Example of using the return value**
     ///
@@ -41,13 +59,76 @@ where
     /// ```
     ///
     pub fn update<R: 'static>(&self, f: impl Fn(&mut T::Inner) -> R) -> R {
-        self.id.rt_ref(|rt| {
-            let (is_equal, r) =
-                rt[self.id].with_signal(self.id, |sig| sig.value().update::<T, R>(f));
-            if !is_equal {
+        SignalError::or_panic(self.try_update(f))
+    }
+
+    /// Like [update](Self::update) but returns an error instead of panicking
+    /// when the signal can't be used.
+    ///
+    /// ```rust
+    /// # use reactive_signals::{signal, runtimes::ClientRuntime, SignalError};
+    /// # let root = ClientRuntime::new_root_scope();
+    /// let sc = root.new_child();
+    /// let count = signal!(sc, 2);
+    /// assert_eq!(count.try_update(|val| *val += 1), Ok(()));
+    ///
+    /// sc.discard();
+    /// assert_eq!(count.try_update(|val| *val += 1), Err(SignalError::ScopeDiscarded));
+    /// ```
+    pub fn try_update<R: 'static>(&self, f: impl Fn(&mut T::Inner) -> R) -> Result<R, SignalError> {
+        self.try_modify(|value| value.update::<T, R>(f))
+    }
+
+    /// Like [update](Self::update) but the function returns `changed`, true when
+    /// it changed the value, and the subscribers are notified only if it did.
+    ///
+    /// ```rust
+    /// # use reactive_signals::{signal, runtimes::ClientRuntime};
+    /// # let sc = ClientRuntime::new_root_scope();
+    /// let names = signal!(sc, vec!["kiwi"]);
+    /// // nothing is removed, so nothing is notified
+    /// names.maybe_update(|names| {
+    ///     let len = names.len();
+    ///     names.retain(|name| !name.is_empty());
+    ///     names.len() != len
+    /// });
+    /// ```
+    pub fn maybe_update(&self, f: impl Fn(&mut T::Inner) -> bool) {
+        self.modify(|value| (value.maybe_update::<T>(f), ()))
+    }
+
+    /// Like [update](Self::update) but the value is cloned before applying the
+    /// function and then compared to the result, so that the subscribers are
+    /// only notified if the value changed.
+    ///
+    /// The comparison is the one used by [set](Self::set), so values that don't
+    /// implement [PartialEq] and don't have a custom comparison always notify.
+    pub fn update_checked<R: 'static>(&self, f: impl Fn(&mut T::Inner) -> R) -> R
+    where
+        T::Inner: Clone,
+    {
+        self.modify(|value| value.update_checked::<T, R>(f))
+    }
+
+    /// Runs `f`, that changes the value and tells if it changed, and notifies
+    /// the subscribers if it did.
+    fn modify<R>(&self, f: impl FnOnce(&AnyData) -> (bool, R)) -> R {
+        SignalError::or_panic(self.try_modify(f))
+    }
+
+    fn try_modify<R>(&self, f: impl FnOnce(&AnyData) -> (bool, R)) -> Result<R, SignalError> {
+        self.id.try_rt_ref(self.generation, |rt| {
+            let (changed, r) = rt[self.id].try_with_signal(self.id, self.version, |sig| {
+                sig.check_writable()?;
+                sig.value().check_access(true)?;
+                Ok(rt.reading(|| f(sig.value())))
+            })?;
+            if changed {
                 propagate_change(rt, self.id);
             }
-            r
+            // f may have changed other signals
+            flush_changes(rt);
+            Ok(r)
         })
     }
 }
@@ -60,7 +141,26 @@ where
 {
     /// Get a copy of the signal value (if the value implements [Copy])
     pub fn get(&self) -> T::Inner {
-        register_and_run(self.id, |sig| sig.value().get::<T>())
+        register_and_run(self.id, self.generation, self.version, |sig| {
+            sig.value().get::<T>()
+        })
+    }
+
+    /// Like [get](Self::get) but returns an error instead of panicking
+    /// when the signal can't be used.
+    pub fn try_get(&self) -> Result<T::Inner, SignalError> {
+        try_register_and_run(self.id, self.generation, self.version, |sig| {
+            sig.value().check_access(false)?;
+            Ok(sig.value().get::<T>())
+        })
+    }
+
+    /// Get a copy of the signal value (if the value implements [Copy])
+    /// without subscribing to it.
+    pub fn peek(&self) -> T::Inner {
+        run_untracked(self.id, self.generation, self.version, |sig| {
+            sig.value().get::<T>()
+        })
     }
 }
 
@@ -74,7 +174,9 @@ where
     ///
     /// Use the `.with()` function if you can in order to avoid the clone.
     pub fn cloned(&self) -> T::Inner {
-        register_and_run(self.id, |sig| sig.value().cloned::<T>())
+        register_and_run(self.id, self.generation, self.version, |sig| {
+            sig.value().cloned::<T>()
+        })
     }
 }
 
@@ -99,7 +201,53 @@ where
     /// ```
     ///
     pub fn with<R: 'static>(&self, f: impl Fn(&T::Inner) -> R) -> R {
-        register_and_run(self.id, |sig| sig.value().with::<T, R>(f))
+        register_and_run(self.id, self.generation, self.version, |sig| {
+            sig.value().with::<T, R>(f)
+        })
+    }
+
+    /// Like [with](Self::with) but returns an error instead of panicking
+    /// when the signal can't be used.
+    pub fn try_with<R: 'static>(&self, f: impl Fn(&T::Inner) -> R) -> Result<R, SignalError> {
+        try_register_and_run(self.id, self.generation, self.version, |sig| {
+            sig.value().check_access(false)?;
+            Ok(sig.value().with::<T, R>(f))
+        })
+    }
+
+    /// Same as `.with()` but without subscribing to the signal.
+    pub fn with_untracked<R: 'static>(&self, f: impl Fn(&T::Inner) -> R) -> R {
+        run_untracked(self.id, self.generation, self.version, |sig| {
+            sig.value().with::<T, R>(f)
+        })
+    }
+}
+
+impl<T, RT> Signal<T, RT>
+where
+    T: SignalType,
+    RT: Runtime,
+{
+    /// Subscribes to the signal without reading its value. Use it in a func signal
+    /// that should re-run when the signal changes but doesn't need the value.
+    ///
+    /// ```rust
+    /// # use reactive_signals::{signal, runtimes::ClientRuntime};
+    /// # let sc = ClientRuntime::new_root_scope();
+    /// let name = signal!(sc, "kiwi");
+    /// let edits = signal!(sc, 0);
+    ///
+    /// // count the edits of the name without reading it
+    /// signal!(sc, move || {
+    ///     name.track();
+    ///     edits.update(|e| *e += 1);
+    /// });
+    ///
+    /// name.set("fig");
+    /// assert_eq!(edits.get(), 2);
+    /// ```
+    pub fn track(&self) {
+        register_and_run(self.id, self.generation, self.version, |_| ())
     }
 }
 
@@ -119,7 +267,11 @@ where
 {
     /// Get a copy of the signal value (if the value implements [Copy])
     pub fn opt_get(&self) -> Option<T::Inner> {
-        Self::SHOULD_RUN.then(|| register_and_run(self.id, |sig| sig.value().get::<T>()))
+        Self::SHOULD_RUN.then(|| {
+            register_and_run(self.id, self.generation, self.version, |sig| {
+                sig.value().get::<T>()
+            })
+        })
     }
 }
 
@@ -133,7 +285,11 @@ where
     ///
     /// Use the `.with()` function if you can in order to avoid the clone.
     pub fn opt_cloned(&self) -> Option<T::Inner> {
-        Self::SHOULD_RUN.then(|| register_and_run(self.id, |sig| sig.value().cloned::<T>()))
+        Self::SHOULD_RUN.then(|| {
+            register_and_run(self.id, self.generation, self.version, |sig| {
+                sig.value().cloned::<T>()
+            })
+        })
     }
 }
 
@@ -158,23 +314,72 @@ where
     /// ```
     ///
     pub fn opt_with<R: 'static>(&self, f: impl Fn(&T::Inner) -> R) -> Option<R> {
-        Self::SHOULD_RUN.then(|| register_and_run(self.id, |sig| sig.value().with::<T, R>(f)))
+        Self::SHOULD_RUN.then(|| {
+            register_and_run(self.id, self.generation, self.version, |sig| {
+                sig.value().with::<T, R>(f)
+            })
+        })
     }
 }
 
 #[inline]
-fn register_and_run<RT: Runtime, T: 'static, F: FnOnce(&SignalInner<RT>) -> T>(
+fn run_untracked<RT: Runtime, T: 'static, F: FnOnce(&SignalInner<RT>) -> T>(
     id: SignalId<RT>,
+    generation: u32,
+    version: u16,
     f: F,
 ) -> T {
-    id.rt_ref(|rt| {
-        if let Some(listener) = rt.get_running_signal() {
-            rt[id].with_signal(id, |signal| {
-                signal.listeners.insert(listener);
-                f(signal)
-            })
-        } else {
-            rt[id].with_signal(id, |signal| f(signal))
-        }
+    SignalError::or_panic(id.try_rt_ref(generation, |rt| {
+        let val = rt[id].try_with_signal(id, version, |signal| {
+            if signal.state() != SignalState::Clean {
+                update_if_necessary(rt, id);
+            }
+            Ok(rt.reading(|| f(signal)))
+        });
+        flush_changes(rt);
+        val
+    }))
+}
+
+#[inline]
+pub(super) fn register_and_run<RT: Runtime, T: 'static, F: FnOnce(&SignalInner<RT>) -> T>(
+    id: SignalId<RT>,
+    generation: u32,
+    version: u16,
+    f: F,
+) -> T {
+    SignalError::or_panic(try_register_and_run(id, generation, version, |signal| {
+        Ok(f(signal))
+    }))
+}
+
+/// Like `register_and_run` but returns the errors, including the ones of `f`.
+#[inline]
+fn try_register_and_run<RT, T, F>(
+    id: SignalId<RT>,
+    generation: u32,
+    version: u16,
+    f: F,
+) -> Result<T, SignalError>
+where
+    RT: Runtime,
+    T: 'static,
+    F: FnOnce(&SignalInner<RT>) -> Result<T, SignalError>,
+{
+    id.try_rt_ref(generation, |rt| {
+        rt.try_check_running()?;
+        let val = rt[id].try_with_signal(id, version, |signal| {
+            if signal.state() != SignalState::Clean {
+                update_if_necessary(rt, id);
+            }
+            // a frozen signal never changes, so there's no need to listen to it
+            if rt.get_running_signal().is_some() && signal.mode() != RunMode::Frozen {
+                rt.track_source(id);
+            }
+            rt.reading(|| f(signal))
+        });
+        // the signals that ran, or the reader, may have changed other signals
+        flush_changes(rt);
+        val
     })
 }
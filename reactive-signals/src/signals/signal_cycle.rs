@@ -0,0 +1,59 @@
+use std::fmt;
+
+use super::{Signal, SignalId, SignalType};
+use crate::runtimes::Runtime;
+
+/// A func signal changed a signal it depends on, which would make it run again
+/// while it is running, or the changes made by func signals keep causing new changes.
+///
+/// In debug builds the runtime panics with this error. In release builds the
+/// propagation of the change is stopped and the error can be taken with
+/// [Scope::take_signal_cycle](crate::Scope::take_signal_cycle).
+///
+/// Signals don't have names, as storing one would add to the size of every
+/// signal. They are displayed by their scope and their index in it instead,
+/// e.g. `0ᴺ1ˢⁱᵍ` for the second signal created in the root scope, and
+/// [contains](Self::contains) tells if a signal is part of the cycle.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SignalCycle<RT: Runtime> {
+    path: Vec<SignalId<RT>>,
+}
+
+impl<RT: Runtime> SignalCycle<RT> {
+    pub(crate) fn new(path: &[SignalId<RT>]) -> Self {
+        Self {
+            path: path.to_vec(),
+        }
+    }
+
+    /// Whether `signal` is one of the signals that make up the cycle.
+    pub fn contains<T: SignalType>(&self, signal: Signal<T, RT>) -> bool {
+        self.path.contains(&signal.id)
+    }
+}
+
+/// Shows the signals that make up the cycle, starting and ending with the
+/// signal that was re-entered. For changes that keep causing new changes,
+/// it is the signals that were changed last.
+impl<RT: Runtime> fmt::Display for SignalCycle<RT> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "signal cycle detected: ")?;
+        for (i, id) in self.path.iter().enumerate() {
+            if i > 0 {
+                write!(f, " -> ")?;
+            }
+            write!(f, "{id:?}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<RT: Runtime> fmt::Debug for SignalCycle<RT> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SignalCycle")
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+impl<RT: Runtime> std::error::Error for SignalCycle<RT> {}
@@ -0,0 +1,40 @@
+use crate::{primitives::DynFunc, runtimes::Runtime};
+
+use super::{Func, Readable, Signal, SignalType};
+
+impl<T, RT> Signal<T, RT>
+where
+    T: SignalType + Readable,
+    RT: Runtime,
+{
+    /// Creates a func signal, in the same scope, that accumulates the values of
+    /// this signal. It starts with `f(&init, value)` and then, each time this
+    /// signal changes, it becomes `f(&previous, value)`.
+    ///
+    /// ```rust
+    /// # use reactive_signals::{signal, runtimes::ClientRuntime};
+    /// # let sc = ClientRuntime::new_root_scope();
+    /// let count = signal!(sc, 1);
+    /// let total = count.scan(0, |total, count| total + count);
+    ///
+    /// count.set(2);
+    /// count.set(5);
+    /// assert_eq!(total.get(), 8);
+    /// ```
+    pub fn scan<U: 'static>(
+        &self,
+        init: U,
+        f: impl Fn(&U, &T::Inner) -> U + 'static,
+    ) -> Signal<Func<U>, RT>
+    where
+        RT: 'static,
+    {
+        let sig = *self;
+        let sx = self.scope();
+        Signal::func(sx, || {
+            DynFunc::reducer::<_, U, Func<U>>(move |acc: Option<&U>| {
+                sig.with(|value| f(acc.unwrap_or(&init), value))
+            })
+        })
+    }
+}
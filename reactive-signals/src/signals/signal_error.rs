@@ -0,0 +1,52 @@
+use std::fmt;
+
+/// Why a signal couldn't be used, as returned by the `try_` accessors such as
+/// [try_get](crate::Signal::try_get). The other accessors panic with it.
+///
+/// Such a panic, or any other panic of a signal, can be caught with
+/// [catch_unwind](std::panic::catch_unwind): the propagation it interrupted
+/// is abandoned and the runtime keeps propagating the next changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignalError {
+    /// The scope of the signal has been discarded.
+    ScopeDiscarded,
+    /// The signal has been [disposed](crate::Signal::dispose).
+    Disposed,
+    /// The runtime of the signal has been discarded, which discards all its scopes.
+    RuntimeDiscarded,
+    /// The runtime of the signal doesn't exist here, for instance because the
+    /// signal was created on another thread, or it is read by a signal of
+    /// another runtime, which can't subscribe to it.
+    WrongRuntime,
+    /// The value is already in use, for instance when reading a signal in the
+    /// function given to its own `.update`.
+    ///
+    /// It is only detected without the `unsafe-cell` feature.
+    BorrowConflict,
+    /// The signal has been [frozen](crate::Signal::freeze) and can't be changed.
+    Frozen,
+}
+
+impl SignalError {
+    /// Panics with the error, for the accessors that don't return it.
+    #[track_caller]
+    pub(crate) fn or_panic<T>(result: Result<T, SignalError>) -> T {
+        result.unwrap_or_else(|err| panic!("{err}"))
+    }
+}
+
+impl fmt::Display for SignalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            Self::ScopeDiscarded => "the scope of the signal has been discarded",
+            Self::Disposed => "the signal has been disposed",
+            Self::RuntimeDiscarded => "the runtime of the signal has been discarded",
+            Self::WrongRuntime => "the runtime of the signal isn't the one in use",
+            Self::BorrowConflict => "the value of the signal is already in use",
+            Self::Frozen => "the signal has been frozen",
+        };
+        f.write_str(msg)
+    }
+}
+
+impl std::error::Error for SignalError {}
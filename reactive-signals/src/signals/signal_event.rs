@@ -0,0 +1,142 @@
+use std::marker::PhantomData;
+
+use crate::{runtimes::Runtime, scope::Scope};
+
+use super::{
+    signal_accessors::register_and_run, signal_new::new_event, updater::propagate_change, SignalId,
+};
+
+/// A signal without a value that tells its subscribers that something
+/// happened, such as a refresh or a submit. It is created with
+/// [Scope::trigger](crate::Scope::trigger).
+///
+/// ```rust
+/// # use reactive_signals::{signal, runtimes::ClientRuntime};
+/// # let sc = ClientRuntime::new_root_scope();
+/// let refresh = sc.trigger();
+/// let loads = signal!(sc, 0);
+///
+/// signal!(sc, move || {
+///     refresh.track();
+///     loads.update(|l| *l += 1);
+/// });
+///
+/// refresh.notify();
+/// assert_eq!(loads.get(), 2);
+/// ```
+pub struct Trigger<RT: Runtime> {
+    id: SignalId<RT>,
+    generation: u32,
+    version: u16,
+}
+
+impl<RT: Runtime> Clone for Trigger<RT> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<RT: Runtime> Copy for Trigger<RT> {}
+
+impl<RT: Runtime> Trigger<RT> {
+    pub(crate) fn new(sx: Scope<RT>) -> Self {
+        let (id, version) = new_event(sx);
+        Self {
+            id,
+            generation: sx.generation,
+            version,
+        }
+    }
+
+    /// Notifies the subscribers.
+    pub fn notify(&self) {
+        self.id
+            .checked_rt_ref(self.generation, |rt| propagate_change(rt, self.id));
+    }
+
+    /// Subscribes to the trigger.
+    pub fn track(&self) {
+        register_and_run(self.id, self.generation, self.version, |_| ())
+    }
+}
+
+/// A signal that hands a payload to its subscribers without storing it.
+/// It is created with [Scope::event](crate::Scope::event).
+///
+/// The payload is only available to the signals that run while the event is
+/// propagated, which are the func signals and the effects but not the lazy
+/// signals. When it is emitted several times in a batch, the subscribers
+/// only get the last payload.
+///
+/// ```rust
+/// # use reactive_signals::{signal, runtimes::ClientRuntime};
+/// # let sc = ClientRuntime::new_root_scope();
+/// let clicked = sc.event::<(i32, i32)>();
+/// let clicks = signal!(sc, Vec::new());
+///
+/// signal!(sc, effect, move || {
+///     if let Some(pos) = clicked.cloned() {
+///         clicks.update(|c| c.push(pos));
+///     }
+/// });
+///
+/// clicked.emit((4, 2));
+/// assert_eq!(clicks.cloned(), vec![(4, 2)]);
+/// // the payload was dropped once it was handed out
+/// assert_eq!(clicked.cloned(), None);
+/// ```
+pub struct Event<T: 'static, RT: Runtime> {
+    id: SignalId<RT>,
+    generation: u32,
+    version: u16,
+    ty: PhantomData<T>,
+}
+
+impl<T: 'static, RT: Runtime> Clone for Event<T, RT> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: 'static, RT: Runtime> Copy for Event<T, RT> {}
+
+impl<T: 'static, RT: Runtime> Event<T, RT> {
+    pub(crate) fn new(sx: Scope<RT>) -> Self {
+        let (id, version) = new_event(sx);
+        Self {
+            id,
+            generation: sx.generation,
+            version,
+            ty: PhantomData,
+        }
+    }
+
+    /// Hands `payload` to the subscribers and then drops it.
+    pub fn emit(&self, payload: T) {
+        self.id.checked_rt_ref(self.generation, |rt| {
+            rt[self.id].with_signal(self.id, |sig| sig.payload().set(payload));
+            propagate_change(rt, self.id);
+        });
+    }
+
+    /// Applies a function to the payload, which is `None` when the event isn't
+    /// being propagated, and returns whatever that function returns.
+    pub fn with<R: 'static>(&self, f: impl FnOnce(Option<&T>) -> R) -> R {
+        register_and_run(self.id, self.generation, self.version, |sig| {
+            sig.payload().with(f)
+        })
+    }
+
+    /// Get a clone of the payload, if the event is being propagated.
+    pub fn cloned(&self) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.with(|payload| payload.cloned())
+    }
+
+    /// Subscribes to the event without reading the payload.
+    pub fn track(&self) {
+        register_and_run(self.id, self.generation, self.version, |_| ())
+    }
+}
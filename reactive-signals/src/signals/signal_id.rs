@@ -2,7 +2,7 @@ use std::cmp::Ordering;
 
 use crate::arena_tree::NodeId;
 
-use crate::{primitives::u15Bool, runtimes::RuntimeInner, scope::Scope, Runtime};
+use crate::{primitives::u15Bool, runtimes::RuntimeInner, scope::Scope, Runtime, SignalError};
 
 /// The SignalId has three components:
 ///
@@ -13,11 +13,9 @@ use crate::{primitives::u15Bool, runtimes::RuntimeInner, scope::Scope, Runtime};
 ///
 /// ## Ordering and equality
 ///
-/// The SignalId doesn't use the `rt` for ordering or equality operations,
-/// because it is up to the user to make sure that signals are not used
-/// across runtimes.
-///
-/// They are ordered by Scope and then by `id`.
+/// The signals of different runtimes are never equal. They are ordered by
+/// Scope and then by `id`, which is only used within a runtime, where the
+/// `rt` is the same for all of them.
 #[derive(Clone, Copy)]
 pub(crate) struct SignalId<RT: Runtime> {
     pub(crate) id: u15Bool,
@@ -44,44 +42,52 @@ impl<RT: Runtime> SignalId<RT> {
         self.id.as_usize()
     }
 
+    /// Runs `f` with the runtime. Panics if the scope of the signal has been
+    /// discarded since it was of the given generation, which is the one of
+    /// the handle.
     #[inline]
-    pub(crate) fn rt_ref<F, T>(&self, f: F) -> T
+    pub(crate) fn checked_rt_ref<F, T>(&self, generation: u32, f: F) -> T
     where
         F: FnOnce(&RuntimeInner<RT>) -> T,
     {
-        self.rt.with_ref(f)
+        SignalError::or_panic(self.try_rt_ref(generation, |rt| Ok(f(rt))))
+    }
+
+    /// Like `checked_rt_ref` but returns an error instead of panicking.
+    #[inline]
+    pub(crate) fn try_rt_ref<F, T>(&self, generation: u32, f: F) -> Result<T, SignalError>
+    where
+        F: FnOnce(&RuntimeInner<RT>) -> Result<T, SignalError>,
+    {
+        self.rt.try_with_ref(|rt| {
+            rt.try_check_scope(self.sx, generation)?;
+            f(rt)
+        })
     }
 }
 
 impl<RT: Runtime> PartialEq for SignalId<RT> {
     #[inline]
     fn eq(&self, other: &SignalId<RT>) -> bool {
-        self.id == other.id && self.sx == other.sx
+        self.id == other.id && self.sx == other.sx && self.rt == other.rt
     }
 }
 
 impl<RT: Runtime> Eq for SignalId<RT> {}
 
 // ordering by NodeId (Scope) and then id. The runtime is not considered
-// as it is assumed to be the same for all SignalId's running on the same
-// thread
+// as the SignalIds are only sorted within a runtime
 impl<RT: Runtime> PartialOrd for SignalId<RT> {
     #[inline]
     fn partial_cmp(&self, other: &SignalId<RT>) -> Option<Ordering> {
-        match self.sx.partial_cmp(&other.sx) {
-            Some(Ordering::Equal) => self.id.partial_cmp(&other.id),
-            cmp => cmp,
-        }
+        Some(self.cmp(other))
     }
 }
 
 impl<RT: Runtime> Ord for SignalId<RT> {
     #[inline]
     fn cmp(&self, other: &SignalId<RT>) -> Ordering {
-        match self.sx.cmp(&other.sx) {
-            Ordering::Equal => self.id.cmp(&other.id),
-            cmp => cmp,
-        }
+        self.sx.cmp(&other.sx).then_with(|| self.id.cmp(&other.id))
     }
 }
 
@@ -131,3 +137,22 @@ fn signal_id_ordering() {
     assert!(sig1_scope2 < sig2_scope2);
     assert!(sig2_scope1 < sig1_scope2);
 }
+
+#[test]
+fn signal_id_runtime() {
+    use crate::runtimes::ServerRuntime;
+
+    let sig1_rt1 = SignalId {
+        id: u15Bool::new(1, false),
+        sx: NodeId::from(1),
+        rt: ServerRuntime::from(1),
+    };
+
+    let sig1_rt2 = SignalId {
+        id: u15Bool::new(1, false),
+        sx: NodeId::from(1),
+        rt: ServerRuntime::from(2),
+    };
+
+    assert_ne!(sig1_rt1, sig1_rt2);
+}
@@ -1,49 +1,157 @@
-use std::fmt::Debug;
+use std::{cell::Cell, fmt::Debug};
 
 use super::SignalId;
 use crate::{
-    primitives::{AnyData, DynFunc, SignalSet},
+    primitives::{AnyData, AnyPayload, DynFunc, SignalSet},
     runtimes::{Runtime, RuntimeInner},
+    SignalError,
 };
 
 #[derive(Debug)]
 pub enum SignalValue {
     Data(AnyData),
     Func(DynFunc),
+    /// A trigger or an event, which has no value.
+    Event(AnyPayload),
     #[cfg(debug_assertions)]
     Reuse,
 }
 
+/// The propagation state of a signal.
+///
+/// When a data signal changes, its direct listeners are marked `Dirty` and all
+/// signals further down are marked `Check`. A `Check` signal only re-runs
+/// if one of its sources turns out to have changed once they have been
+/// brought up to date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub(crate) enum SignalState {
+    #[default]
+    Clean,
+    Check,
+    Dirty,
+}
+
+/// When a func signal runs during a propagation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum RunMode {
+    /// As soon as its sources are up to date.
+    #[default]
+    Eager,
+    /// Only when it is read.
+    Lazy,
+    /// After all the eager signals are up to date.
+    Effect,
+    /// Never, and the value can't be changed either.
+    /// See [freeze](crate::Signal::freeze).
+    Frozen,
+}
+
 #[derive(Debug)]
 pub(crate) struct SignalInner<RT: Runtime> {
     pub(super) value: SignalValue,
     pub(crate) listeners: SignalSet<3, SignalId<RT>>,
+    /// The signals this signal has read from. The back-edges of `listeners`.
+    pub(crate) sources: SignalSet<3, SignalId<RT>>,
+    pub(crate) state: Cell<SignalState>,
+    pub(crate) mode: Cell<RunMode>,
+    /// Changed when the signal is disposed, so that the handles
+    /// to it no longer match once its slot is reused.
+    pub(crate) version: Cell<u16>,
 }
 
 impl<RT: Runtime> SignalInner<RT> {
     pub(crate) fn value(&self) -> &AnyData {
         match self.value {
             SignalValue::Data(ref value) | SignalValue::Func(DynFunc { ref value, .. }) => value,
+            SignalValue::Event(_) => panic!("BUG: reading the value of an event"),
             #[cfg(debug_assertions)]
             SignalValue::Reuse => panic!("BUG: using a reused signal"),
         }
     }
 
+    pub(crate) fn payload(&self) -> &AnyPayload {
+        match self.value {
+            SignalValue::Event(ref payload) => payload,
+            _ => panic!("BUG: reading the payload of a signal that isn't an event"),
+        }
+    }
+
+    /// Drops the payload of an event once it has been propagated.
+    pub(crate) fn end_event(&self) {
+        if let SignalValue::Event(payload) = &self.value {
+            payload.clear();
+        }
+    }
+
+    #[inline]
+    pub(crate) fn state(&self) -> SignalState {
+        self.state.get()
+    }
+
+    #[inline]
+    pub(crate) fn set_state(&self, state: SignalState) {
+        self.state.set(state)
+    }
+
+    #[inline]
+    pub(crate) fn mode(&self) -> RunMode {
+        self.mode.get()
+    }
+
+    #[inline]
+    /// A signal without a value that holds a slot until the signal
+    /// that is created in it is ready.
+    pub(crate) fn reserved() -> Self {
+        SignalInner {
+            value: SignalValue::Event(AnyPayload::default()),
+            listeners: Default::default(),
+            sources: Default::default(),
+            state: Default::default(),
+            mode: Cell::new(RunMode::Frozen),
+            version: Default::default(),
+        }
+    }
+
+    pub(crate) fn version(&self) -> u16 {
+        self.version.get()
+    }
+
+    /// Whether the slot has used up its versions, in which case it isn't
+    /// reused, as the handles to its first signal would match again.
+    pub(crate) fn is_retired(&self) -> bool {
+        self.version() == u16::MAX
+    }
+
+    /// Returns an error if the signal has been frozen.
+    pub(crate) fn check_writable(&self) -> Result<(), SignalError> {
+        match self.mode() {
+            RunMode::Frozen => Err(SignalError::Frozen),
+            _ => Ok(()),
+        }
+    }
+
     pub(crate) fn run(&self, rt: &RuntimeInner<RT>, id: SignalId<RT>) -> bool {
         if let SignalValue::Func(func) = &self.value {
-            let previous = rt.set_running_signal(Some(id));
-            let changed = func.run();
-            // println!("run: {id:?} - {changed}");
-            rt.set_running_signal(previous);
-            changed
+            rt.run_tracked(id, &self.sources, || func.run())
         } else {
             // println!("NOT: {id:?}");
             false
         }
     }
 
+    /// Changes the version so that the handles no longer match. The value is
+    /// only dropped once the slot is reused or the scope discarded, as it
+    /// may be in use.
+    pub(crate) fn dispose(&self) {
+        self.state.set(SignalState::Clean);
+        self.version.set(self.version() + 1);
+        self.end_event();
+    }
+
     pub(crate) fn reuse(&mut self) {
         self.listeners.clear();
+        self.sources.clear();
+        self.state.set(SignalState::Clean);
         #[cfg(debug_assertions)]
         {
             self.value = SignalValue::Reuse;
@@ -0,0 +1,127 @@
+use crate::runtimes::{Runtime, RuntimeInner};
+
+use super::{
+    updater::{flush_changes, rerun, update_if_necessary},
+    Runnable, Signal, SignalError, SignalType,
+};
+
+impl<T, RT> Signal<T, RT>
+where
+    T: SignalType,
+    RT: Runtime,
+{
+    /// Deletes the signal without discarding its scope, so that scopes that live
+    /// long, such as the root scope, don't grow when signals are created on the fly.
+    /// Its slot is reused by the next signal created in the scope.
+    ///
+    /// The signal stops listening to the signals it used and the signals using it
+    /// stop listening to it. Using it afterwards panics, as would a func signal that
+    /// still reads it once it re-runs, and the `try_` accessors return
+    /// [SignalError::Disposed]. Disposing it again does nothing.
+    ///
+    /// It panics when called from the signal's own function.
+    ///
+    /// ```rust
+    /// # use reactive_signals::{signal, runtimes::ClientRuntime, SignalError};
+    /// # let sc = ClientRuntime::new_root_scope();
+    /// let toast = signal!(sc, "Saved");
+    /// assert!(toast.is_alive());
+    ///
+    /// toast.dispose();
+    /// assert!(!toast.is_alive());
+    /// assert_eq!(toast.try_get(), Err(SignalError::Disposed));
+    /// ```
+    pub fn dispose(&self) {
+        let res = self.id.try_rt_ref(self.generation, |rt| {
+            self.check(rt)?;
+            rt.dispose_signal(self.id);
+            Ok(())
+        });
+        match res {
+            Ok(())
+            | Err(SignalError::Disposed)
+            | Err(SignalError::ScopeDiscarded)
+            | Err(SignalError::RuntimeDiscarded) => {}
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Whether the signal can still be used, which it can't once it has been
+    /// disposed or its scope has been discarded.
+    pub fn is_alive(&self) -> bool {
+        let res = self.id.try_rt_ref(self.generation, |rt| self.check(rt));
+        matches!(res, Ok(()) | Err(SignalError::BorrowConflict))
+    }
+
+    /// Makes the signal a constant: it keeps its current value, a func signal no
+    /// longer re-runs and the signals using it stop listening to it, as it won't
+    /// change anymore. Changing the value of a frozen data signal panics and the
+    /// `try_` accessors return [SignalError::Frozen].
+    ///
+    /// It panics when called from the signal's own function.
+    ///
+    /// ```rust
+    /// # use reactive_signals::{signal, runtimes::ClientRuntime};
+    /// # let sc = ClientRuntime::new_root_scope();
+    /// let count = signal!(sc, 1);
+    /// let double = signal!(sc, move || count.get() * 2);
+    ///
+    /// double.freeze();
+    /// count.set(5);
+    /// assert_eq!(double.get(), 2);
+    /// ```
+    pub fn freeze(&self) {
+        SignalError::or_panic(self.id.try_rt_ref(self.generation, |rt| {
+            self.check(rt)?;
+            // a lazy signal may not have a value yet
+            update_if_necessary(rt, self.id);
+            rt.freeze_signal(self.id);
+            flush_changes(rt);
+            Ok(())
+        }))
+    }
+
+    /// Whether no signal listens to the signal, or is about to as
+    /// it has read it while running.
+    pub(crate) fn is_unused(&self) -> bool {
+        self.id.checked_rt_ref(self.generation, |rt| {
+            rt[self.id].with_signal(self.id, |signal| signal.listeners.is_empty())
+                && !rt.is_tracked(self.id)
+        })
+    }
+
+    /// Returns an error if the signal has been disposed.
+    fn check(&self, rt: &RuntimeInner<RT>) -> Result<(), SignalError> {
+        rt[self.id].try_with_signal(self.id, self.version, |_| Ok(()))
+    }
+}
+
+impl<T, RT> Signal<T, RT>
+where
+    T: SignalType + Runnable,
+    RT: Runtime,
+{
+    /// Runs the function again even though none of the signals it uses changed,
+    /// for instance because it reads something that isn't a signal, and notifies
+    /// the subscribers if the value changed. A lazy signal runs once it is read.
+    ///
+    /// ```rust
+    /// # use reactive_signals::{signal, runtimes::ClientRuntime};
+    /// # use std::{cell::Cell, rc::Rc};
+    /// # let sc = ClientRuntime::new_root_scope();
+    /// let clock = Rc::new(Cell::new(1));
+    /// let now = signal!(sc, clone: clock, move || clock.get());
+    ///
+    /// clock.set(2);
+    /// assert_eq!(now.get(), 1);
+    /// now.invalidate();
+    /// assert_eq!(now.get(), 2);
+    /// ```
+    pub fn invalidate(&self) {
+        SignalError::or_panic(self.id.try_rt_ref(self.generation, |rt| {
+            self.check(rt)?;
+            rerun(rt, self.id);
+            Ok(())
+        }))
+    }
+}
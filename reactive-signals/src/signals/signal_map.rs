@@ -0,0 +1,167 @@
+use std::{cell::Cell, collections::HashMap, hash::Hash};
+
+use crate::{primitives::AnyData, runtimes::Runtime, scope::Scope};
+
+use super::{Data, KeySignals, Signal};
+
+/// A reactive map that notifies the readers of a key only when that key changes.
+///
+/// The subscribers can observe:
+///
+/// - a single key, with `.get`, `.with_value` or `.contains_key`, which is notified when
+///   the key is inserted, updated or removed.
+/// - the keys, with `.keys` or `.len`, which is notified when a key is added or removed.
+/// - the whole map, with `.with`, which is notified on every change.
+///
+/// It is created with [Scope::signal_map](crate::Scope::signal_map).
+///
+/// ```rust
+/// # use reactive_signals::{signal, runtimes::ClientRuntime};
+/// # let sc = ClientRuntime::new_root_scope();
+/// let users = sc.signal_map([(1, "kiwi"), (2, "fig")]);
+///
+/// // only re-runs when user 2 changes
+/// let name = signal!(sc, move || users.get(&2).unwrap_or_default().to_uppercase());
+/// let count = signal!(sc, move || users.len());
+///
+/// users.insert(1, "lime");
+/// users.insert(3, "plum");
+/// assert_eq!(count.get(), 3);
+///
+/// users.remove(&2);
+/// assert_eq!(name.cloned(), "");
+/// ```
+pub struct SignalMap<K: 'static, V: 'static, RT: Runtime + 'static> {
+    state: Signal<Data<HashMap<K, V>>, RT>,
+    /// Notified when a key is added or removed.
+    keys: Signal<Data<()>, RT>,
+    /// A signal per key, for the readers of a single key.
+    entries: KeySignals<K, RT>,
+}
+
+impl<K: 'static, V: 'static, RT: Runtime + 'static> Clone for SignalMap<K, V, RT> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K: 'static, V: 'static, RT: Runtime + 'static> Copy for SignalMap<K, V, RT> {}
+
+impl<K, V, RT> SignalMap<K, V, RT>
+where
+    K: Hash + Eq + Clone + 'static,
+    V: 'static,
+    RT: Runtime + 'static,
+{
+    pub(crate) fn new(sx: Scope<RT>, items: HashMap<K, V>) -> Self {
+        Self {
+            state: Signal::data(sx, AnyData::new(Data(items))),
+            keys: Signal::data(sx, AnyData::new(Data(()))),
+            entries: KeySignals::new(sx),
+        }
+    }
+
+    /// Applies `f` to the whole map and returns whatever it returns.
+    pub fn with<R: 'static>(&self, f: impl Fn(&HashMap<K, V>) -> R) -> R {
+        self.state.with(|items| f(items))
+    }
+
+    /// Get a clone of the keys, subscribing to the keys being added or removed.
+    pub fn keys(&self) -> Vec<K> {
+        self.keys.track();
+        self.state
+            .with_untracked(|items| items.keys().cloned().collect())
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.track();
+        self.state.with_untracked(|items| items.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The number of signals of the keys.
+    #[cfg(test)]
+    pub(crate) fn entry_signals(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.with_value(key, |_| ()).is_some()
+    }
+
+    /// Get a clone of the value of `key`, subscribing only to that key.
+    pub fn get(&self, key: &K) -> Option<V>
+    where
+        V: Clone,
+    {
+        self.with_value(key, V::clone)
+    }
+
+    /// Applies `f` to the value of `key`, subscribing only to that key.
+    pub fn with_value<R: 'static>(&self, key: &K, f: impl Fn(&V) -> R) -> Option<R> {
+        match self.entries.get_or_create(key) {
+            Some(entry) => entry.track(),
+            None => self.state.track(),
+        }
+        self.state.with_untracked(|items| items.get(key).map(&f))
+    }
+
+    /// Inserts the value of `key` and returns the previous value, if any.
+    pub fn insert(&self, key: K, value: V) -> Option<V> {
+        let mut previous = None;
+        self.change(&key.clone(), |items| {
+            previous = items.insert(key, value);
+            Some(previous.is_none())
+        });
+        previous
+    }
+
+    /// Applies `f` to the value of `key`, if there is one, and returns
+    /// whatever `f` returns.
+    pub fn update<R>(&self, key: &K, f: impl FnOnce(&mut V) -> R) -> Option<R> {
+        let mut ret = None;
+        self.change(key, |items| {
+            ret = items.get_mut(key).map(f);
+            ret.is_some().then_some(false)
+        });
+        ret
+    }
+
+    /// Removes `key` and returns its value, if any.
+    pub fn remove(&self, key: &K) -> Option<V> {
+        let mut removed = None;
+        self.change(key, |items| {
+            removed = items.remove(key);
+            removed.is_some().then_some(true)
+        });
+        // the readers of the key have been notified and read it again
+        self.entries.release(key);
+        removed
+    }
+
+    /// Applies `op`, which returns whether the keys changed or `None` if nothing
+    /// changed, and notifies the subscribers of the map, of `key` and, if they
+    /// changed, of the keys.
+    fn change(&self, key: &K, op: impl FnOnce(&mut HashMap<K, V>) -> Option<bool>) {
+        let op = Cell::new(Some(op));
+        let changed = Cell::new(None);
+        self.state.scope().batch(|| {
+            self.state.maybe_update(|items| {
+                changed.set((op.take().unwrap())(items));
+                changed.get().is_some()
+            });
+            let Some(keys_changed) = changed.get() else {
+                return;
+            };
+            if let Some(entry) = self.entries.get(key) {
+                entry.set(());
+            }
+            if keys_changed {
+                self.keys.set(());
+            }
+        })
+    }
+}
@@ -1,50 +1,121 @@
-use std::marker::PhantomData;
+use std::{cell::Cell, marker::PhantomData};
 
 use crate::{
-    primitives::{AnyData, DynFunc},
+    primitives::{AnyData, AnyPayload, DynFunc, SignalSet},
     runtimes::Runtime,
     scope::Scope,
     Signal,
 };
 
-use super::{SignalInner, SignalType, SignalValue};
+use super::{
+    updater::flush_changes, RunMode, SignalId, SignalInner, SignalState, SignalType, SignalValue,
+};
 
 impl<T: 'static + SignalType, RT: Runtime> Signal<T, RT> {
     pub(crate) fn data(sx: Scope<RT>, data: AnyData) -> Signal<T, RT> {
-        let id = sx.rt.with_ref(|rt| {
+        let (id, version) = sx.rt_ref(|rt| {
             let scope = &rt.scope_tree[sx.sx];
             let id = scope.next_signal_id(sx);
             let signal = SignalInner {
                 value: SignalValue::Data(data),
                 listeners: Default::default(),
+                sources: Default::default(),
+                state: Default::default(),
+                mode: Cell::new(RunMode::Eager),
+                version: Default::default(),
             };
-            scope.insert_signal(signal);
-            id
+            let version = scope.insert_signal(id, signal);
+            (id, version)
         });
         Signal {
             id,
+            generation: sx.generation,
+            version,
             ty: PhantomData,
         }
     }
 
     pub(crate) fn func(sx: Scope<RT>, func: impl FnOnce() -> DynFunc) -> Signal<T, RT> {
-        let id = sx.rt.with_ref(|rt| {
+        Self::run_func(sx, RunMode::Eager, func)
+    }
+
+    /// Creates an effect, which runs after the func signals during a propagation.
+    pub(crate) fn effect(sx: Scope<RT>, func: impl FnOnce() -> DynFunc) -> Signal<T, RT> {
+        Self::run_func(sx, RunMode::Effect, func)
+    }
+
+    fn run_func(sx: Scope<RT>, mode: RunMode, func: impl FnOnce() -> DynFunc) -> Signal<T, RT> {
+        let (id, version) = sx.rt_ref(|rt| {
             let scope = &rt.scope_tree[sx.sx];
             let id = scope.next_signal_id(sx);
+            // the slot is taken before the function runs, as it may create
+            // signals in the same scope
+            let version = scope.insert_signal(id, SignalInner::reserved());
 
-            let previous = rt.set_running_signal(Some(id));
+            let sources = SignalSet::default();
+            let value = rt.run_tracked(id, &sources, func);
             let signal = SignalInner {
-                value: SignalValue::Func(func()),
+                value: SignalValue::Func(value),
                 listeners: Default::default(),
+                sources,
+                state: Default::default(),
+                mode: Cell::new(mode),
+                version: Default::default(),
             };
-            rt.set_running_signal(previous);
 
-            scope.insert_signal(signal);
-            id
+            scope.replace_signal(id, signal);
+            (id, version)
         });
+        // the function may have changed other signals
+        sx.rt_ref(flush_changes);
         Signal {
             id,
+            generation: sx.generation,
+            version,
             ty: PhantomData,
         }
     }
+
+    /// Creates a func signal that doesn't run until it is read.
+    pub(crate) fn lazy_func(sx: Scope<RT>, func: DynFunc) -> Signal<T, RT> {
+        let (id, version) = sx.rt_ref(|rt| {
+            let scope = &rt.scope_tree[sx.sx];
+            let id = scope.next_signal_id(sx);
+            let signal = SignalInner {
+                value: SignalValue::Func(func),
+                listeners: Default::default(),
+                sources: Default::default(),
+                state: Cell::new(SignalState::Dirty),
+                mode: Cell::new(RunMode::Lazy),
+                version: Default::default(),
+            };
+            let version = scope.insert_signal(id, signal);
+            (id, version)
+        });
+        Signal {
+            id,
+            generation: sx.generation,
+            version,
+            ty: PhantomData,
+        }
+    }
+}
+
+/// Creates a signal without a value, for a trigger or an event, and
+/// returns its id and version.
+pub(super) fn new_event<RT: Runtime>(sx: Scope<RT>) -> (SignalId<RT>, u16) {
+    sx.rt_ref(|rt| {
+        let scope = &rt.scope_tree[sx.sx];
+        let id = scope.next_signal_id(sx);
+        let signal = SignalInner {
+            value: SignalValue::Event(AnyPayload::default()),
+            listeners: Default::default(),
+            sources: Default::default(),
+            state: Default::default(),
+            mode: Cell::new(RunMode::Eager),
+            version: Default::default(),
+        };
+        let version = scope.insert_signal(id, signal);
+        (id, version)
+    })
 }
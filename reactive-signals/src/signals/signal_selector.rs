@@ -0,0 +1,109 @@
+use std::{cell::Cell, hash::Hash};
+
+use crate::{
+    primitives::{AnyData, DynFunc},
+    runtimes::Runtime,
+};
+
+use super::{Data, Func, KeySignals, Readable, Signal, SignalType};
+
+/// Tells if a signal has a given value, notifying only the readers of the
+/// previous and the new value when it changes. It is created with
+/// [selector](Signal::selector).
+pub struct Selector<K: 'static, RT: Runtime + 'static> {
+    /// The current value of the signal, which the readers that couldn't get
+    /// a key signal subscribe to.
+    current: Signal<Data<K>, RT>,
+    /// Keeps `current` up to date with the signal.
+    updater: Signal<Func<()>, RT>,
+    /// A signal per key, for the readers of a single key.
+    keys: KeySignals<K, RT>,
+}
+
+impl<K: 'static, RT: Runtime + 'static> Clone for Selector<K, RT> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K: 'static, RT: Runtime + 'static> Copy for Selector<K, RT> {}
+
+impl<T, RT> Signal<T, RT>
+where
+    T: SignalType + Readable,
+    T::Inner: Hash + Eq + Clone + 'static,
+    RT: Runtime + 'static,
+{
+    /// Creates a [Selector], in the same scope, for the value of this signal.
+    ///
+    /// ```rust
+    /// # use reactive_signals::{signal, runtimes::ClientRuntime};
+    /// # let sc = ClientRuntime::new_root_scope();
+    /// let selected = signal!(sc, 1);
+    /// let selector = selected.selector();
+    ///
+    /// // only the rows 1 and 2 re-run when the selection changes from 1 to 2
+    /// let rows = (0..1000)
+    ///     .map(|id| signal!(sc, move || selector.is(&id)))
+    ///     .collect::<Vec<_>>();
+    ///
+    /// selected.set(2);
+    /// assert!(!rows[1].get());
+    /// assert!(rows[2].get());
+    /// ```
+    pub fn selector(&self) -> Selector<T::Inner, RT> {
+        let sig = *self;
+        let sx = self.scope();
+        let current: Signal<Data<T::Inner>, RT> =
+            Signal::data(sx, AnyData::new(Data(self.with_untracked(T::Inner::clone))));
+        let keys = KeySignals::new(sx);
+
+        let updater = Signal::func(sx, || {
+            DynFunc::new::<_, (), Func<()>>(move || {
+                let value = sig.with(T::Inner::clone);
+                let previous = Cell::new(None);
+                // notifies the readers that couldn't get a key signal
+                current.maybe_update(|current| {
+                    if *current == value {
+                        return false;
+                    }
+                    previous.set(Some(std::mem::replace(current, value.clone())));
+                    true
+                });
+                if let Some(previous) = previous.take() {
+                    let changed = keys.get_all([previous, value].into_iter());
+                    changed.iter().for_each(|key| key.set(()));
+                }
+            })
+        });
+        Selector {
+            current,
+            updater,
+            keys,
+        }
+    }
+}
+
+impl<K, RT> Selector<K, RT>
+where
+    K: Hash + Eq + Clone + 'static,
+    RT: Runtime + 'static,
+{
+    /// The number of key signals.
+    #[cfg(test)]
+    pub(crate) fn key_signals(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Whether the signal's value is `key`, subscribing only to that key.
+    pub fn is(&self, key: &K) -> bool {
+        // bring the state up to date if the signal changed
+        self.updater.with_untracked(|_| ());
+        match self.keys.get_or_create(key) {
+            Some(sig) => sig.track(),
+            // notified whenever the value changes
+            None => self.current.track(),
+        }
+        self.current.with_untracked(|current| current == key)
+    }
+}
@@ -0,0 +1,146 @@
+use std::cell::Cell;
+
+use crate::{primitives::DynFunc, runtimes::Runtime};
+
+use super::{EqFunc, Modifiable, Readable, Signal, SignalType};
+
+/// A part of the value of a data [Signal], usually a field, that can be read
+/// and changed on its own. It is created with [slice](Signal::slice).
+///
+/// The subscribers of a slice are only notified when the part changes, and
+/// changing the part only notifies the subscribers of the whole value when
+/// it actually changes.
+pub struct SignalSlice<T: SignalType, U: PartialEq + 'static, RT: Runtime> {
+    signal: Signal<T, RT>,
+    /// Holds a copy of the part, which is only changed when the part changes.
+    part: Signal<EqFunc<U>, RT>,
+    part_mut: fn(&mut T::Inner) -> &mut U,
+}
+
+impl<T: SignalType, U: PartialEq + 'static, RT: Runtime> Clone for SignalSlice<T, U, RT> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: SignalType, U: PartialEq + 'static, RT: Runtime> Copy for SignalSlice<T, U, RT> {}
+
+impl<T, RT> Signal<T, RT>
+where
+    T: SignalType + Readable + Modifiable,
+    RT: Runtime,
+{
+    /// Creates a [SignalSlice], in the same scope, for the part of the value
+    /// given by `part` and `part_mut`.
+    ///
+    /// ```rust
+    /// # use reactive_signals::{signal, runtimes::ClientRuntime};
+    /// # let sc = ClientRuntime::new_root_scope();
+    /// #[derive(PartialEq)]
+    /// struct AppState {
+    ///     count: i32,
+    ///     name: String,
+    /// }
+    ///
+    /// let state = signal!(sc, AppState { count: 1, name: "kiwi".to_string() });
+    /// let count = state.slice(|s| &s.count, |s| &mut s.count);
+    ///
+    /// // only re-runs when the count changes
+    /// let double = signal!(sc, move || count.get() * 2);
+    ///
+    /// state.update(|s| s.name = "fig".to_string());
+    /// count.set(3);
+    /// assert_eq!(double.get(), 6);
+    /// assert_eq!(state.with(|s| s.count), 3);
+    /// ```
+    pub fn slice<U>(
+        &self,
+        part: fn(&T::Inner) -> &U,
+        part_mut: fn(&mut T::Inner) -> &mut U,
+    ) -> SignalSlice<T, U, RT>
+    where
+        U: PartialEq + Clone + 'static,
+        RT: 'static,
+    {
+        let sig = *self;
+        let sx = self.scope();
+        let part = Signal::func(sx, || {
+            DynFunc::new::<_, U, EqFunc<U>>(move || sig.with(|value| part(value).clone()))
+        });
+        SignalSlice {
+            signal: sig,
+            part,
+            part_mut,
+        }
+    }
+}
+
+impl<T, U, RT> SignalSlice<T, U, RT>
+where
+    T: SignalType + Modifiable,
+    U: PartialEq + Clone + 'static,
+    RT: Runtime,
+{
+    /// Sets the part and notifies the subscribers if it changed.
+    pub fn set(&self, val: U) {
+        let val = Cell::new(Some(val));
+        self.signal.maybe_update(|value| {
+            let part = (self.part_mut)(value);
+            let val = val.take().unwrap();
+            let changed = *part != val;
+            *part = val;
+            changed
+        });
+    }
+
+    /// Applies a function to the part to mutate it in place and returns
+    /// whatever that function returns. The subscribers are notified if
+    /// the part changed.
+    pub fn update<R: 'static>(&self, f: impl Fn(&mut U) -> R) -> R {
+        let ret = Cell::new(None);
+        self.signal.maybe_update(|value| {
+            let part = (self.part_mut)(value);
+            let before = part.clone();
+            ret.set(Some(f(part)));
+            *part != before
+        });
+        ret.take().unwrap()
+    }
+}
+
+impl<T, U, RT> SignalSlice<T, U, RT>
+where
+    T: SignalType,
+    U: PartialEq + Copy + 'static,
+    RT: Runtime,
+{
+    /// Get a copy of the part (if it implements [Copy])
+    pub fn get(&self) -> U {
+        self.part.get()
+    }
+}
+
+impl<T, U, RT> SignalSlice<T, U, RT>
+where
+    T: SignalType,
+    U: PartialEq + 'static,
+    RT: Runtime,
+{
+    /// Get a clone of the part.
+    pub fn cloned(&self) -> U
+    where
+        U: Clone,
+    {
+        self.part.cloned()
+    }
+
+    /// Applies a function to the part and returns whatever that function returns.
+    pub fn with<R: 'static>(&self, f: impl Fn(&U) -> R) -> R {
+        self.part.with(f)
+    }
+
+    /// Same as `.with()` but without subscribing to the part.
+    pub fn with_untracked<R: 'static>(&self, f: impl Fn(&U) -> R) -> R {
+        self.part.with_untracked(f)
+    }
+}
@@ -0,0 +1,191 @@
+use crate::runtimes::Runtime;
+
+use super::{Modifiable, Readable, Signal, SignalError, SignalType};
+
+/// A handle to a [Signal] that can only be read. It is created with
+/// [read_only](Signal::read_only) or [split](Signal::split).
+///
+/// It is handed out to code that should follow a signal but not change it.
+pub struct ReadSignal<T: SignalType, RT: Runtime> {
+    signal: Signal<T, RT>,
+}
+
+/// A handle to a data [Signal] that can only be changed. It is created with
+/// [write_only](Signal::write_only) or [split](Signal::split).
+pub struct WriteSignal<T: SignalType, RT: Runtime> {
+    signal: Signal<T, RT>,
+}
+
+impl<T: SignalType, RT: Runtime> Clone for ReadSignal<T, RT> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: SignalType, RT: Runtime> Copy for ReadSignal<T, RT> {}
+
+impl<T: SignalType, RT: Runtime> Clone for WriteSignal<T, RT> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: SignalType, RT: Runtime> Copy for WriteSignal<T, RT> {}
+
+impl<T, RT> Signal<T, RT>
+where
+    T: SignalType + Readable,
+    RT: Runtime,
+{
+    /// A handle to the signal that can only be read.
+    pub fn read_only(&self) -> ReadSignal<T, RT> {
+        ReadSignal { signal: *self }
+    }
+}
+
+impl<T, RT> Signal<T, RT>
+where
+    T: SignalType + Modifiable,
+    RT: Runtime,
+{
+    /// A handle to the signal that can only be changed.
+    pub fn write_only(&self) -> WriteSignal<T, RT> {
+        WriteSignal { signal: *self }
+    }
+}
+
+impl<T, RT> Signal<T, RT>
+where
+    T: SignalType + Readable + Modifiable,
+    RT: Runtime,
+{
+    /// Splits the signal into a handle that can only be read and one that
+    /// can only be changed, so that the owner of the state can keep the
+    /// [WriteSignal] and hand out the [ReadSignal].
+    ///
+    /// ```rust
+    /// use reactive_signals::{signal, runtimes::{ClientRuntime, Runtime}, types::*, ReadSignal};
+    ///
+    /// fn double<RT: Runtime>(count: ReadSignal<EqData<i32>, RT>) -> i32 {
+    ///     // count.set(0) doesn't compile
+    ///     count.get() * 2
+    /// }
+    ///
+    /// let sc = ClientRuntime::new_root_scope();
+    /// let (count, set_count) = signal!(sc, 1).split();
+    /// set_count.set(4);
+    /// assert_eq!(double(count), 8);
+    /// ```
+    pub fn split(&self) -> (ReadSignal<T, RT>, WriteSignal<T, RT>) {
+        (self.read_only(), self.write_only())
+    }
+}
+
+impl<T, RT> From<Signal<T, RT>> for ReadSignal<T, RT>
+where
+    T: SignalType + Readable,
+    RT: Runtime,
+{
+    fn from(signal: Signal<T, RT>) -> Self {
+        signal.read_only()
+    }
+}
+
+impl<T, RT> ReadSignal<T, RT>
+where
+    T: SignalType + Readable,
+    T::Inner: Copy,
+    RT: Runtime,
+{
+    /// See [Signal::get].
+    pub fn get(&self) -> T::Inner {
+        self.signal.get()
+    }
+
+    /// See [Signal::peek].
+    pub fn peek(&self) -> T::Inner {
+        self.signal.peek()
+    }
+
+    /// See [Signal::try_get].
+    pub fn try_get(&self) -> Result<T::Inner, SignalError> {
+        self.signal.try_get()
+    }
+}
+
+impl<T, RT> ReadSignal<T, RT>
+where
+    T: SignalType + Readable,
+    T::Inner: Clone,
+    RT: Runtime,
+{
+    /// See [Signal::cloned].
+    pub fn cloned(&self) -> T::Inner {
+        self.signal.cloned()
+    }
+}
+
+impl<T, RT> ReadSignal<T, RT>
+where
+    T: SignalType + Readable,
+    RT: Runtime,
+{
+    /// See [Signal::with].
+    pub fn with<R: 'static>(&self, f: impl Fn(&T::Inner) -> R) -> R {
+        self.signal.with(f)
+    }
+
+    /// See [Signal::try_with].
+    pub fn try_with<R: 'static>(&self, f: impl Fn(&T::Inner) -> R) -> Result<R, SignalError> {
+        self.signal.try_with(f)
+    }
+
+    /// See [Signal::with_untracked].
+    pub fn with_untracked<R: 'static>(&self, f: impl Fn(&T::Inner) -> R) -> R {
+        self.signal.with_untracked(f)
+    }
+
+    /// See [Signal::track].
+    pub fn track(&self) {
+        self.signal.track()
+    }
+}
+
+impl<T, RT> WriteSignal<T, RT>
+where
+    T: SignalType + Modifiable,
+    RT: Runtime,
+{
+    /// See [Signal::set].
+    pub fn set(&self, val: T::Inner) {
+        self.signal.set(val)
+    }
+
+    /// See [Signal::try_set].
+    pub fn try_set(&self, val: T::Inner) -> Result<(), SignalError> {
+        self.signal.try_set(val)
+    }
+
+    /// See [Signal::update].
+    pub fn update<R: 'static>(&self, f: impl Fn(&mut T::Inner) -> R) -> R {
+        self.signal.update(f)
+    }
+
+    /// See [Signal::try_update].
+    pub fn try_update<R: 'static>(&self, f: impl Fn(&mut T::Inner) -> R) -> Result<R, SignalError> {
+        self.signal.try_update(f)
+    }
+
+    /// See [Signal::maybe_update].
+    pub fn maybe_update(&self, f: impl Fn(&mut T::Inner) -> bool) {
+        self.signal.maybe_update(f)
+    }
+
+    /// See [Signal::update_checked].
+    pub fn update_checked<R: 'static>(&self, f: impl Fn(&mut T::Inner) -> R) -> R
+    where
+        T::Inner: Clone,
+    {
+        self.signal.update_checked(f)
+    }
+}
@@ -0,0 +1,322 @@
+use std::{cell::Cell, ops::Range, rc::Rc};
+
+use crate::{
+    primitives::{AnyData, DynFunc},
+    runtimes::Runtime,
+    scope::Scope,
+};
+
+use super::{Data, Effect, KeySignals, Signal};
+
+/// A change made to a [SignalVec], as observed with [on_diff](SignalVec::on_diff).
+///
+/// Applying the diffs, in order, to a copy of the vec keeps it the same as the [SignalVec].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VecDiff<T> {
+    Push { value: T },
+    Insert { index: usize, value: T },
+    Remove { index: usize },
+    Move { from: usize, to: usize },
+    Set { index: usize, value: T },
+}
+
+/// An observer of the diffs, created with [on_diff](SignalVec::on_diff).
+struct Observer<RT: Runtime> {
+    /// The position of the next diff the observer is to see.
+    cursor: Rc<Cell<usize>>,
+    /// The effect of the observer, once it is created. The observer is
+    /// forgotten when it is disposed.
+    effect: Option<Signal<Effect, RT>>,
+}
+
+/// The diffs that haven't been seen by all the observers yet.
+struct DiffLog<T, RT: Runtime> {
+    /// The position of the first diff, counted from the creation of the vec.
+    start: usize,
+    diffs: Vec<VecDiff<T>>,
+    observers: Vec<Observer<RT>>,
+}
+
+impl<T, RT: Runtime> DiffLog<T, RT> {
+    fn end(&self) -> usize {
+        self.start + self.diffs.len()
+    }
+
+    fn since(&self, cursor: usize) -> &[VecDiff<T>] {
+        &self.diffs[cursor - self.start..]
+    }
+
+    /// Records the diff, if anybody observes them, and drops the
+    /// diffs that all the observers have seen.
+    fn record(&mut self, diff: VecDiff<T>) {
+        self.observers
+            .retain(|observer| observer.effect.iter().all(|effect| effect.is_alive()));
+        let seen = self.observers.iter().map(|o| o.cursor.get()).min();
+        let seen = seen.unwrap_or_else(|| self.end());
+        self.diffs.drain(..seen - self.start);
+        self.start = seen;
+        if self.observers.is_empty() {
+            self.start += 1;
+        } else {
+            self.diffs.push(diff);
+        }
+    }
+}
+
+struct VecState<T, RT: Runtime> {
+    items: Vec<T>,
+    log: DiffLog<T, RT>,
+}
+
+/// A reactive vec that tells its subscribers what changed.
+///
+/// The subscribers can observe:
+///
+/// - the whole vec, with `.with`, `.cloned` or `.len`, which is notified on every change.
+/// - a single index, with `.get` or `.with_item`, which is only notified when the value
+///   at that index changes, including when it is moved by an insert or a remove.
+/// - the [VecDiff]s, with [on_diff](Self::on_diff).
+///
+/// It is created with [Scope::signal_vec](crate::Scope::signal_vec).
+///
+/// ```rust
+/// # use reactive_signals::{signal, runtimes::ClientRuntime, VecDiff};
+/// # use std::{cell::RefCell, rc::Rc};
+/// # let sc = ClientRuntime::new_root_scope();
+/// let rows = sc.signal_vec(vec!["kiwi", "fig"]);
+///
+/// // only re-runs when the second row changes
+/// let second = signal!(sc, move || rows.get(1).unwrap_or_default().len());
+///
+/// let diffs = Rc::new(RefCell::new(Vec::new()));
+/// let d = diffs.clone();
+/// rows.on_diff(move |diff| d.borrow_mut().push(diff.clone()));
+///
+/// rows.push("plum");
+/// rows.set(0, "lime");
+/// assert_eq!(second.get(), 3);
+///
+/// rows.remove(0);
+/// assert_eq!(second.get(), 4);
+/// assert_eq!(diffs.borrow()[2], VecDiff::Remove { index: 0 });
+/// ```
+pub struct SignalVec<T: 'static, RT: Runtime + 'static> {
+    state: Signal<Data<VecState<T, RT>>, RT>,
+    /// A signal per index, for the readers of a single index.
+    indices: KeySignals<usize, RT>,
+    /// Notified when the length changes, for the readers of an index
+    /// that is out of bounds.
+    len: Signal<Data<()>, RT>,
+}
+
+impl<T: 'static, RT: Runtime + 'static> Clone for SignalVec<T, RT> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: 'static, RT: Runtime + 'static> Copy for SignalVec<T, RT> {}
+
+impl<T, RT> SignalVec<T, RT>
+where
+    T: Clone + 'static,
+    RT: Runtime + 'static,
+{
+    pub(crate) fn new(sx: Scope<RT>, items: Vec<T>) -> Self {
+        let state: VecState<T, RT> = VecState {
+            items,
+            log: DiffLog {
+                start: 0,
+                diffs: Vec::new(),
+                observers: Vec::new(),
+            },
+        };
+        Self {
+            state: Signal::data(sx, AnyData::new(Data(state))),
+            indices: KeySignals::new(sx),
+            len: Signal::data(sx, AnyData::new(Data(()))),
+        }
+    }
+
+    /// Applies `f` to the whole vec and returns whatever it returns.
+    pub fn with<R: 'static>(&self, f: impl Fn(&[T]) -> R) -> R {
+        self.state.with(|state| f(&state.items))
+    }
+
+    /// Get a clone of the whole vec.
+    pub fn cloned(&self) -> Vec<T> {
+        self.state.with(|state| state.items.clone())
+    }
+
+    pub fn len(&self) -> usize {
+        self.state.with(|state| state.items.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The number of diffs kept for the observers.
+    #[cfg(test)]
+    pub(crate) fn logged_diffs(&self) -> usize {
+        self.state.with_untracked(|state| state.log.diffs.len())
+    }
+
+    /// Get a clone of the value at `index`, subscribing only to that index.
+    pub fn get(&self, index: usize) -> Option<T> {
+        self.with_item(index, T::clone)
+    }
+
+    /// Applies `f` to the value at `index`, subscribing only to that index.
+    ///
+    /// When `index` is out of bounds, it subscribes to the length instead.
+    pub fn with_item<R: 'static>(&self, index: usize, f: impl Fn(&T) -> R) -> Option<R> {
+        if index >= self.state.with_untracked(|state| state.items.len()) {
+            self.len.track();
+            return None;
+        }
+        match self.indices.get_or_create(&index) {
+            Some(sig) => sig.track(),
+            None => self.state.track(),
+        }
+        self.state
+            .with_untracked(|state| state.items.get(index).map(&f))
+    }
+
+    /// Calls `f` with every change made to the vec from now on. The changes are
+    /// given once all the signals affected by them are up to date, as for effects.
+    ///
+    /// `f` must not change the vec.
+    pub fn on_diff(&self, f: impl Fn(&VecDiff<T>) + 'static) -> Signal<Effect, RT> {
+        let cursor = Rc::new(Cell::new(0));
+        self.state.maybe_update(|state| {
+            let log = &mut state.log;
+            cursor.set(log.end());
+            log.observers.push(Observer {
+                cursor: cursor.clone(),
+                effect: None,
+            });
+            false
+        });
+        let state = self.state;
+        let seen = cursor.clone();
+        let effect = Signal::effect(state.scope(), || {
+            DynFunc::effect(move || {
+                state.with(|state| {
+                    let log = &state.log;
+                    log.since(seen.get()).iter().for_each(&f);
+                    seen.set(log.end());
+                })
+            })
+        });
+        self.state.maybe_update(|state| {
+            let observers = state.log.observers.iter_mut();
+            observers
+                .filter(|observer| Rc::ptr_eq(&observer.cursor, &cursor))
+                .for_each(|observer| observer.effect = Some(effect));
+            false
+        });
+        effect
+    }
+
+    pub fn push(&self, value: T) {
+        self.change(|state| {
+            let index = state.items.len();
+            state.items.push(value.clone());
+            state.log.record(VecDiff::Push { value });
+            index..index + 1
+        })
+    }
+
+    /// Inserts `value` at `index`, shifting the values after it.
+    ///
+    /// Panics if `index` is larger than the length.
+    pub fn insert(&self, index: usize, value: T) {
+        self.change(|state| {
+            state.items.insert(index, value.clone());
+            state.log.record(VecDiff::Insert { index, value });
+            index..state.items.len()
+        })
+    }
+
+    /// Removes the value at `index`, shifting the values after it.
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove(&self, index: usize) -> T {
+        let mut removed = None;
+        self.change(|state| {
+            let len = state.items.len();
+            removed = Some(state.items.remove(index));
+            state.log.record(VecDiff::Remove { index });
+            index..len
+        });
+        removed.unwrap()
+    }
+
+    /// Moves the value at `from` to `to`, shifting the values in between.
+    ///
+    /// Panics if either index is out of bounds.
+    pub fn move_item(&self, from: usize, to: usize) {
+        self.change(|state| {
+            let value = state.items.remove(from);
+            state.items.insert(to, value);
+            state.log.record(VecDiff::Move { from, to });
+            from.min(to)..from.max(to) + 1
+        })
+    }
+
+    /// Replaces the value at `index`.
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn set(&self, index: usize, value: T) {
+        self.change(|state| {
+            state.items[index] = value.clone();
+            state.log.record(VecDiff::Set { index, value });
+            index..index + 1
+        })
+    }
+
+    /// Keeps only the values for which `f` returns true.
+    pub fn retain(&self, mut f: impl FnMut(&T) -> bool) {
+        self.change(|state| {
+            let len = state.items.len();
+            let mut index = 0;
+            let mut removed = Vec::new();
+            state.items.retain(|value| {
+                let keep = f(value);
+                if !keep {
+                    removed.push(index);
+                }
+                index += 1;
+                keep
+            });
+            // from the back so that the indexes are valid when applied in order
+            removed
+                .iter()
+                .rev()
+                .for_each(|index| state.log.record(VecDiff::Remove { index: *index }));
+            removed.first().map_or(len..len, |first| *first..len)
+        })
+    }
+
+    /// Applies `op`, which returns the range of indexes it changed, and
+    /// notifies the subscribers of the vec, of those indexes and, if it
+    /// changed, of the length.
+    fn change(&self, op: impl FnOnce(&mut VecState<T, RT>) -> Range<usize>) {
+        let op = Cell::new(Some(op));
+        let changed = Cell::new((0..0, false));
+        self.state.scope().batch(|| {
+            self.state.update(|state| {
+                let len = state.items.len();
+                let range = (op.take().unwrap())(state);
+                changed.set((range, len != state.items.len()));
+            });
+            let (range, len_changed) = changed.take();
+            let indices = self.indices.get_all(range);
+            indices.iter().for_each(|index| index.set(()));
+            if len_changed {
+                self.len.set(());
+            }
+        })
+    }
+}
@@ -46,6 +46,46 @@ fn test_signal_update() {
     assert_eq!(history.borrow().join(", "), "Hi 1, Hi 2");
 }
 
+#[test]
+fn test_signal_update_change_detection() {
+    let sc = ServerRuntime::new_root_scope();
+
+    let list_sig = signal!(sc, vec![1, 2, 3]);
+
+    let runs = Rc::new(Cell::new(0));
+    signal!(sc, clone: runs, move || {
+        list_sig.track();
+        runs.inc();
+    });
+    assert_eq!(runs.get(), 1);
+
+    // EqData<Vec<_>> isn't hashed, so update always notifies
+    list_sig.update(|list| list.retain(|n| *n > 0));
+    assert_eq!(runs.get(), 2);
+
+    list_sig.update_checked(|list| list.retain(|n| *n > 0));
+    assert_eq!(runs.get(), 2);
+    list_sig.update_checked(|list| list.retain(|n| *n > 1));
+    assert_eq!(runs.get(), 3);
+
+    let clear_long = |list: &mut Vec<i32>| {
+        let long = list.len() > 2;
+        if long {
+            list.clear();
+        }
+        long
+    };
+    list_sig.maybe_update(clear_long);
+    assert_eq!(runs.get(), 3);
+    assert_eq!(list_sig.cloned(), vec![2, 3]);
+
+    list_sig.update(|list| list.push(4));
+    assert_eq!(runs.get(), 4);
+    list_sig.maybe_update(clear_long);
+    assert_eq!(runs.get(), 5);
+    assert!(list_sig.with(|list| list.is_empty()));
+}
+
 #[test]
 fn test_signal_func_val() {
     let sc = ServerRuntime::new_root_scope();
@@ -63,7 +103,31 @@ fn test_signal_func_val() {
 
     num_sig.set(4);
 
-    assert_eq!(output.values(), "a5-b5, a4-b5, a4-b4");
+    assert_eq!(output.values(), "a5-b5, a4-b4");
+}
+
+#[test]
+fn test_signal_diamond() {
+    let sc = ServerRuntime::new_root_scope();
+
+    let num_sig = signal!(sc, 1);
+
+    let a_sig = signal!(sc, move || num_sig.get() + 1);
+    let b_sig = signal!(sc, move || num_sig.get() * 10);
+    let c_sig = signal!(sc, move || a_sig.get() + b_sig.get());
+
+    let output = Rc::new(StringStore::new());
+    let d_call = Rc::new(Cell::new(0usize));
+    let _d_sig = signal!(sc, clone: output d_call, move || {
+        d_call.inc();
+        output.push(format!("{}={}+{}", c_sig.get(), a_sig.get(), b_sig.get()))
+    });
+
+    num_sig.set(2);
+    num_sig.set(3);
+
+    assert_eq!(output.values(), "12=2+10, 23=3+20, 34=4+30");
+    assert_eq!(d_call.get(), 3);
 }
 
 #[test]
@@ -109,6 +173,1011 @@ fn test_signal_func_skip_equal() {
     assert_eq!(c_call.get(), 1);
 }
 
+#[test]
+fn test_signal_eq_func_stops_propagation() {
+    let sc = ServerRuntime::new_root_scope();
+
+    let num_sig = signal!(sc, 1);
+    let is_odd = signal!(sc, move || num_sig.get() % 2 == 1);
+
+    let output = Rc::new(StringStore::new());
+    let _txt_sig = signal!(sc, clone: output, move || {
+        output.push(format!("odd: {}", is_odd.get()))
+    });
+
+    num_sig.set(3);
+    num_sig.set(5);
+    assert_eq!(output.values(), "odd: true");
+
+    num_sig.set(6);
+    assert_eq!(output.values(), "odd: true, odd: false");
+}
+
+#[test]
+fn test_signal_drops_stale_dependencies() {
+    let sc = ServerRuntime::new_root_scope();
+
+    let use_a = signal!(sc, true);
+    let a_sig = signal!(sc, 1);
+    let b_sig = signal!(sc, 10);
+
+    let call = Rc::new(Cell::new(0usize));
+    let sum_sig = signal!(sc, clone: call, move || {
+        call.inc();
+        if use_a.get() {
+            a_sig.get()
+        } else {
+            b_sig.get()
+        }
+    });
+
+    a_sig.set(2);
+    assert_eq!(sum_sig.get(), 2);
+    assert_eq!(call.get(), 2);
+
+    // b is not read yet
+    b_sig.set(20);
+    assert_eq!(call.get(), 2);
+
+    use_a.set(false);
+    assert_eq!(sum_sig.get(), 20);
+    assert_eq!(call.get(), 3);
+
+    // a is no longer read
+    a_sig.set(3);
+    assert_eq!(call.get(), 3);
+
+    b_sig.set(30);
+    assert_eq!(sum_sig.get(), 30);
+    assert_eq!(call.get(), 4);
+}
+
+#[test]
+fn test_signal_untracked_reads() {
+    let sc = ServerRuntime::new_root_scope();
+
+    let a_sig = signal!(sc, 1);
+    let b_sig = signal!(sc, "b1".to_string());
+    let c_sig = signal!(sc, 100);
+
+    let output = Rc::new(StringStore::new());
+    let _str_sig = signal!(sc, clone: output, move || {
+        let b = b_sig.with_untracked(|b| b.clone());
+        let c = sc.untrack(|| c_sig.get());
+        output.push(format!("{}-{b}-{c}", a_sig.peek()));
+        c_sig.track();
+    });
+
+    a_sig.set(2);
+    b_sig.set("b2".to_string());
+    assert_eq!(output.values(), "1-b1-100");
+
+    c_sig.set(200);
+    assert_eq!(output.values(), "1-b1-100, 2-b2-200");
+}
+
+#[test]
+fn test_signal_lazy_func() {
+    let sc = ServerRuntime::new_root_scope();
+
+    let num_sig = signal!(sc, 1);
+
+    let runs = Rc::new(Cell::new(0));
+    let lazy_sig = signal!(sc, lazy, clone: runs, move || {
+        runs.inc();
+        num_sig.get() * 10
+    });
+    assert_eq!(runs.get(), 0);
+
+    num_sig.set(2);
+    num_sig.set(3);
+    assert_eq!(runs.get(), 0);
+
+    assert_eq!(lazy_sig.get(), 30);
+    assert_eq!(lazy_sig.get(), 30);
+    assert_eq!(runs.get(), 1);
+
+    num_sig.set(4);
+    assert_eq!(runs.get(), 1);
+
+    // an eager listener pulls the lazy signal when it changes
+    let output = Rc::new(StringStore::new());
+    let _str_sig = signal!(sc, clone: output, move || output.push(format!("{}", lazy_sig.get())));
+    assert_eq!(runs.get(), 2);
+
+    num_sig.set(5);
+    assert_eq!(runs.get(), 3);
+    assert_eq!(output.values(), "40, 50");
+}
+
+#[test]
+#[cfg_attr(
+    debug_assertions,
+    should_panic(expected = "signal cycle detected: 0ᴺ1ˢⁱᵍ -> 0ᴺ0ˢⁱᵍ -> 0ᴺ1ˢⁱᵍ")
+)]
+fn test_signal_cycle() {
+    let sc = ServerRuntime::new_root_scope();
+
+    let num_sig = signal!(sc, 0);
+    let incr_sig = signal!(sc, move || {
+        let num = num_sig.get();
+        if num == 1 {
+            num_sig.set(num + 1);
+        }
+    });
+
+    num_sig.set(1);
+
+    // only reached in release builds
+    let cycle = sc.take_signal_cycle().unwrap();
+    assert_eq!(
+        cycle.to_string(),
+        "signal cycle detected: 0ᴺ1ˢⁱᵍ -> 0ᴺ0ˢⁱᵍ -> 0ᴺ1ˢⁱᵍ"
+    );
+    assert!(cycle.contains(num_sig) && cycle.contains(incr_sig));
+    assert_eq!(num_sig.get(), 2);
+    assert!(sc.take_signal_cycle().is_none());
+
+    // the runtime is still usable
+    num_sig.set(3);
+    assert!(sc.take_signal_cycle().is_none());
+}
+
+#[test]
+fn test_signal_write_from_func() {
+    let sc = ServerRuntime::new_root_scope();
+
+    let num_sig = signal!(sc, 1);
+    let double_sig = signal!(sc, 0);
+    let _sync_sig = signal!(sc, move || double_sig.set(num_sig.get() * 2));
+    assert_eq!(double_sig.get(), 2);
+
+    let output = Rc::new(StringStore::new());
+    let _str_sig = signal!(sc, clone: output, move || {
+        output.push(format!("{}-{}", num_sig.get(), double_sig.get()))
+    });
+
+    // the change of double_sig is propagated after the change of num_sig
+    num_sig.set(2);
+    assert_eq!(output.values(), "1-2, 2-4, 2-4");
+
+    // a change made while reading is propagated after the read
+    num_sig.with(|num| double_sig.set(num * 3));
+    assert_eq!(output.values(), "1-2, 2-4, 2-4, 2-6");
+}
+
+#[test]
+#[cfg_attr(
+    debug_assertions,
+    should_panic(expected = "signal cycle detected: 0ᴺ0ˢⁱᵍ")
+)]
+fn test_signal_write_loop() {
+    let sc = ServerRuntime::new_root_scope();
+
+    let a_sig = signal!(sc, 0);
+    let b_sig = signal!(sc, 0);
+    let _a_to_b = signal!(sc, move || b_sig.set(a_sig.get() + 1));
+    let _b_to_a = signal!(sc, move || a_sig.set(b_sig.get() + 1));
+
+    // only reached in release builds
+    assert!(sc.take_signal_cycle().is_some());
+}
+
+#[test]
+fn test_signal_panic_in_wave() {
+    use std::panic::catch_unwind;
+
+    let sc = ServerRuntime::new_root_scope();
+    let num_sig = signal!(sc, 1);
+    let _panic_sig = signal!(sc, move || assert_ne!(num_sig.get(), 2));
+    let _cycle_sig = signal!(sc, move || {
+        if num_sig.get() == 4 {
+            num_sig.set(5);
+        }
+    });
+    let ten_sig = signal!(sc, move || num_sig.get() * 10);
+
+    assert!(catch_unwind(|| num_sig.set(2)).is_err());
+    // the runtime is still usable
+    num_sig.set(3);
+    assert_eq!(ten_sig.get(), 30);
+
+    // a cycle panics in debug builds
+    let _ = catch_unwind(|| num_sig.set(4));
+    sc.take_signal_cycle();
+    num_sig.set(6);
+    assert_eq!(ten_sig.get(), 60);
+
+    assert!(catch_unwind(|| num_sig.set(2)).is_err());
+    sc.discard();
+
+    // and so is the runtime once it is reused
+    let sc = ServerRuntime::new_root_scope();
+    let num_sig = signal!(sc, 1);
+    let ten_sig = signal!(sc, move || num_sig.get() * 10);
+    num_sig.set(3);
+    assert_eq!(ten_sig.get(), 30);
+}
+
+#[test]
+fn test_signal_effect_runs_last() {
+    let sc = ServerRuntime::new_root_scope();
+
+    let num_sig = signal!(sc, 1);
+
+    let output = Rc::new(StringStore::new());
+    let _effect = signal!(sc, effect, clone: output, move || {
+        output.push(format!("effect: {}", num_sig.get()))
+    });
+    let double_sig = signal!(sc, clone: output, move || {
+        output.push("double".to_string());
+        num_sig.get() * 2
+    });
+    let _log = signal!(sc, effect, clone: output, move || {
+        output.push(format!("log: {}", double_sig.get()))
+    });
+    let _triple_sig = signal!(sc, clone: output, move || {
+        output.push("triple".to_string());
+        num_sig.get() * 3
+    });
+    assert_eq!(output.values(), "effect: 1, double, log: 2, triple");
+
+    num_sig.set(2);
+    assert_eq!(
+        output.values(),
+        "effect: 1, double, log: 2, triple, double, triple, effect: 2, log: 4"
+    );
+}
+
+#[test]
+fn test_signal_custom_eq() {
+    let sc = ServerRuntime::new_root_scope();
+
+    #[derive(PartialEq)]
+    struct Doc {
+        version: u32,
+        text: String,
+    }
+
+    let doc_sig = signal!(sc, cmp: |a, b| a.version == b.version, Doc {
+        version: 1,
+        text: "draft".to_string(),
+    });
+    let float_sig = signal!(sc, 1.0_f64);
+    let rounded_sig = signal!(sc, cmp: |a, b| a == b, move || float_sig.get().round());
+
+    let output = Rc::new(StringStore::new());
+    let _str_sig = signal!(sc, clone: output, move || {
+        let text = doc_sig.with(|doc| doc.text.clone());
+        output.push(format!("{text}-{}", rounded_sig.get()))
+    });
+
+    // same version, not propagated
+    doc_sig.set(Doc {
+        version: 1,
+        text: "edited".to_string(),
+    });
+    float_sig.set(1.2);
+    assert_eq!(output.values(), "draft-1");
+
+    doc_sig.set(Doc {
+        version: 2,
+        text: "final".to_string(),
+    });
+    float_sig.set(2.4);
+    assert_eq!(output.values(), "draft-1, final-1, final-2");
+}
+
+#[test]
+fn test_signal_reducer() {
+    let sc = ServerRuntime::new_root_scope();
+
+    let num_sig = signal!(sc, 1);
+
+    // a running total of the values of num_sig
+    let total_sig = signal!(sc, move |total: Option<&i32>| {
+        total.copied().unwrap_or(0) + num_sig.get()
+    });
+
+    // the difference since last time, for a value that doesn't implement PartialEq
+    struct Diff(i32, i32);
+    let diff_sig = signal!(sc, move |prev: Option<&Diff>| {
+        let num = num_sig.get();
+        Diff(num, num - prev.map_or(0, |prev| prev.0))
+    });
+
+    let count_sig = num_sig.scan(0, |count, _| count + 1);
+
+    num_sig.set(4);
+    num_sig.set(3);
+    assert_eq!(total_sig.get(), 8);
+    assert_eq!(diff_sig.with(|diff| diff.1), -1);
+    assert_eq!(count_sig.get(), 3);
+}
+
+#[test]
+fn test_signal_explicit_kind() {
+    use crate::{runtimes::Runtime, types::EqData, Scope, Signal};
+
+    fn eq_signal<T: PartialEq + 'static, RT: Runtime>(
+        sc: Scope<RT>,
+        value: T,
+    ) -> Signal<EqData<T>, RT> {
+        signal!(sc, eq: value)
+    }
+
+    let sc = ServerRuntime::new_root_scope();
+
+    let num_sig = eq_signal(sc, 1);
+    let hash_sig = signal!(sc, hash: "a");
+    let always_sig = signal!(sc, always: 1);
+
+    let runs = Rc::new(Cell::new(0));
+    let r = runs.clone();
+    let len_sig = signal!(sc, eq: move || hash_sig.get().len() + num_sig.get());
+    let _always_func = signal!(sc, always: move || {
+        r.inc();
+        len_sig.get() + always_sig.get()
+    });
+    assert_eq!(runs.get(), 1);
+
+    num_sig.set(1);
+    hash_sig.set("b");
+    assert_eq!(runs.get(), 1);
+
+    always_sig.set(1);
+    assert_eq!(runs.get(), 2);
+
+    let doubled = sc.eq_func(move || always_sig.get() * 2);
+    let data = sc.eq_data(3);
+    assert_eq!(doubled.get() + data.get(), 5);
+}
+
+#[test]
+fn test_signal_hash_eq_func() {
+    use crate::{
+        types::{ClientHashEqFunc, EqFunc, HashEqFunc, ServerHashEqFunc},
+        Signal,
+    };
+
+    let sc = ServerRuntime::new_root_scope();
+    let num_sig = signal!(sc, 12);
+
+    // only selected when asked for
+    let rendered: Signal<HashEqFunc<String>, _> =
+        signal!(sc, hash: move || format!("{} items", num_sig.get().min(20)));
+    let _server: Signal<ServerHashEqFunc<Vec<i32>>, _> =
+        signal!(sc, server, hash: move || vec![num_sig.get()]);
+    let _client: Signal<ClientHashEqFunc<Vec<i32>>, _> =
+        signal!(sc, client, hash: move || vec![num_sig.get()]);
+    let _string: Signal<EqFunc<String>, _> = signal!(sc, move || num_sig.get().to_string());
+
+    let output = Rc::new(StringStore::new());
+    let out = output.clone();
+    signal!(sc, move || out.push(rendered.cloned()));
+
+    num_sig.set(25);
+    num_sig.set(30);
+    assert_eq!(output.values(), "12 items, 20 items");
+}
+
+#[test]
+fn test_signal_hash_eq_func_collision() {
+    use std::hash::{Hash, Hasher};
+
+    // every value has the same hash
+    #[derive(PartialEq, Clone, Copy)]
+    struct Id(i32);
+    impl Hash for Id {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            0.hash(state)
+        }
+    }
+
+    let sc = ServerRuntime::new_root_scope();
+    let num_sig = signal!(sc, 1);
+    let hash_sig = signal!(sc, hash: move || Id(num_sig.get() / 2));
+    let eq_sig = signal!(sc, move || Id(num_sig.get() / 2));
+
+    let output = Rc::new(StringStore::new());
+    signal!(sc, clone: output, move || output
+        .push(format!("{} {}", hash_sig.get().0, eq_sig.get().0)));
+
+    num_sig.set(2);
+    // the values with the same hash are taken as equal
+    assert_eq!(output.values(), "0 0, 0 1");
+}
+
+#[test]
+fn test_signal_split() {
+    use crate::{ReadSignal, SignalError};
+
+    let sc = ServerRuntime::new_root_scope();
+
+    let (name, set_name) = signal!(sc, "kiwi".to_string()).split();
+    let count = signal!(sc, 1);
+    let count_read: ReadSignal<_, _> = count.into();
+    let set_count = count.write_only();
+
+    let text = signal!(sc, move || format!(
+        "{} {}",
+        count_read.get(),
+        name.cloned()
+    ));
+
+    set_name.set("fig".to_string());
+    set_count.update(|c| *c += 1);
+    assert_eq!(text.cloned(), "2 fig");
+
+    // the read handle works for func signals as well
+    let text_read = text.read_only();
+    set_count.set(3);
+    assert_eq!(text_read.with(|t| t.len()), 5);
+
+    let flag_sig = signal!(sc, true);
+    let (flag, set_flag) = flag_sig.split();
+    assert_eq!(set_flag.try_update(|f| *f = false), Ok(()));
+    assert_eq!(flag.try_get(), Ok(false));
+    flag_sig.dispose();
+    assert_eq!(flag.try_with(|f| *f), Err(SignalError::Disposed));
+    assert_eq!(set_flag.try_set(true), Err(SignalError::Disposed));
+}
+
+#[test]
+fn test_signal_slice() {
+    #[derive(Clone, PartialEq)]
+    struct AppState {
+        count: i32,
+        names: Vec<&'static str>,
+    }
+
+    let sc = ServerRuntime::new_root_scope();
+    let state = signal!(
+        sc,
+        AppState {
+            count: 1,
+            names: vec!["kiwi"]
+        }
+    );
+    let count = state.slice(|s| &s.count, |s| &mut s.count);
+    let names = state.slice(|s| &s.names, |s| &mut s.names);
+
+    let output = Rc::new(StringStore::new());
+    let out = output.clone();
+    signal!(sc, move || out.push(format!("count: {}", count.get())));
+    let out = output.clone();
+    signal!(sc, move || names.with(|n| out.push(n.join(" "))));
+    let out = output.clone();
+    signal!(sc, move || state
+        .with(|s| out.push(format!("state: {}", s.count))));
+
+    names.update(|n| n.push("fig"));
+    // unchanged, nothing is notified
+    count.set(1);
+    count.update(|c| *c += 1);
+    state.update(|s| s.names.clear());
+
+    assert_eq!(
+        output.values(),
+        "count: 1, kiwi, state: 1, kiwi fig, state: 1, count: 2, state: 2, , state: 2"
+    );
+    assert_eq!(names.cloned(), Vec::<&str>::new());
+}
+
+#[test]
+fn test_signal_vec() {
+    use crate::VecDiff;
+    use std::cell::RefCell;
+
+    let sc = ServerRuntime::new_root_scope();
+    let rows = sc.signal_vec(vec![1, 2, 3]);
+
+    let output = Rc::new(StringStore::new());
+    let out = output.clone();
+    signal!(sc, move || out.push(format!("row 2: {:?}", rows.get(2))));
+    let out = output.clone();
+    signal!(sc, move || out.push(format!("len: {}", rows.len())));
+
+    // a copy kept up to date with the diffs
+    let copy = Rc::new(RefCell::new(rows.cloned()));
+    let c = copy.clone();
+    rows.on_diff(move |diff| {
+        let mut copy = c.borrow_mut();
+        match diff.clone() {
+            VecDiff::Push { value } => copy.push(value),
+            VecDiff::Insert { index, value } => copy.insert(index, value),
+            VecDiff::Remove { index } => {
+                copy.remove(index);
+            }
+            VecDiff::Move { from, to } => {
+                let value = copy.remove(from);
+                copy.insert(to, value);
+            }
+            VecDiff::Set { index, value } => copy[index] = value,
+        }
+    });
+
+    rows.push(4);
+    rows.set(0, 10);
+    rows.insert(1, 5);
+    rows.move_item(0, 3);
+    sc.batch(|| {
+        rows.retain(|v| v % 2 == 0);
+        rows.push(6);
+    });
+    assert_eq!(rows.remove(0), 2);
+
+    assert_eq!(rows.cloned(), vec![10, 4, 6]);
+    assert_eq!(*copy.borrow(), rows.cloned());
+    assert_eq!(
+        output.values(),
+        "row 2: Some(3), len: 3, len: 4, len: 4, len: 5, row 2: Some(2), \
+         len: 5, row 2: Some(3), len: 4, row 2: Some(4), len: 3, row 2: Some(6)"
+    );
+}
+
+#[test]
+fn test_signal_vec_out_of_bounds() {
+    let sc = ServerRuntime::new_root_scope();
+    let rows = sc.signal_vec(vec![1, 2, 3]);
+
+    let output = Rc::new(StringStore::new());
+    signal!(sc, clone: output, move || output
+        .push(format!("row 3: {:?}", rows.get(3))));
+
+    // no signal is created for an index that is out of bounds
+    assert_eq!(rows.get(40_000), None);
+
+    rows.set(0, 10);
+    rows.push(4);
+    rows.remove(0);
+    assert_eq!(output.values(), "row 3: None, row 3: Some(4), row 3: None");
+}
+
+#[test]
+fn test_signal_vec_many_readers() {
+    super::key_signals::set_signals_per_scope(8);
+    let sc = ServerRuntime::new_root_scope();
+    let rows = sc.signal_vec(vec![0; 40]);
+
+    // more readers than the index signals a scope holds
+    let readers = (0..40)
+        .map(|i| signal!(sc, move || rows.get(i).unwrap()))
+        .collect::<Vec<_>>();
+
+    rows.set(39, 5);
+    assert_eq!(readers[39].get(), 5);
+
+    // read once outside of the signals, which adds a scope
+    assert_eq!(rows.get(38), Some(0));
+    let runs = Rc::new(Cell::new(0));
+    let last = signal!(sc, clone: runs, move || {
+        runs.set(runs.get() + 1);
+        rows.get(38).unwrap()
+    });
+
+    rows.set(0, 1);
+    rows.set(38, 7);
+    assert_eq!(last.get(), 7);
+    assert_eq!(runs.get(), 2);
+}
+
+#[test]
+fn test_signal_vec_created_by_func() {
+    let sc = ServerRuntime::new_root_scope();
+    let child = sc.new_child();
+    let num_sig = signal!(sc, 1);
+
+    // no scope can be added for the index signals meanwhile
+    let rows_sig = signal!(sc, move || sc.signal_vec(vec![1, 2]));
+    let copies_sig = signal!(sc, move || child.signal_vec(vec![num_sig.get()]));
+
+    let output = Rc::new(StringStore::new());
+    signal!(sc, clone: output, move || output
+        .push(format!("{:?} {:?}", rows_sig.get().get(1), copies_sig.get().get(0))));
+
+    rows_sig.get().set(1, 3);
+    num_sig.set(2);
+    copies_sig.get().set(0, 4);
+    assert_eq!(
+        output.values(),
+        "Some(2) Some(1), Some(3) Some(1), Some(3) Some(2), Some(3) Some(4)"
+    );
+}
+
+#[test]
+fn test_signal_vec_observer_disposed() {
+    let sc = ServerRuntime::new_root_scope();
+    let rows = sc.signal_vec(Vec::new());
+
+    let seen = Rc::new(Cell::new(0));
+    let s = seen.clone();
+    let observer = rows.on_diff(move |_| s.set(s.get() + 1));
+    let _other = rows.on_diff(|_| ());
+
+    rows.push(1);
+    assert_eq!(seen.get(), 1);
+
+    // the diffs are no longer kept for the disposed observer, only
+    // the last one is until the next change, as the other one has
+    // seen it since it was recorded
+    observer.dispose();
+    (0..10).for_each(|i| rows.push(i));
+    assert_eq!(seen.get(), 1);
+    assert_eq!(rows.logged_diffs(), 1);
+}
+
+#[test]
+fn test_signal_map() {
+    let sc = ServerRuntime::new_root_scope();
+    let users = sc.signal_map([(1, "kiwi".to_string()), (2, "fig".to_string())]);
+
+    let output = Rc::new(StringStore::new());
+    let out = output.clone();
+    signal!(sc, move || out.push(format!("1: {:?}", users.get(&1))));
+    let out = output.clone();
+    signal!(sc, move || out
+        .push(format!("3: {}", users.contains_key(&3))));
+    let out = output.clone();
+    signal!(sc, move || out.push(format!("len: {}", users.len())));
+
+    users.update(&2, |name| name.push('s'));
+    users.insert(3, "plum".to_string());
+    users.update(&1, |name| name.make_ascii_uppercase());
+    assert_eq!(users.remove(&3), Some("plum".to_string()));
+    assert_eq!(users.remove(&3), None);
+
+    assert_eq!(
+        output.values(),
+        "1: Some(\"kiwi\"), 3: false, len: 2, len: 3, 3: true, \
+         1: Some(\"KIWI\"), len: 2, 3: false"
+    );
+    let mut keys = users.keys();
+    keys.sort();
+    assert_eq!(keys, vec![1, 2]);
+    assert_eq!(users.with(|map| map[&2].clone()), "figs");
+}
+
+#[test]
+fn test_signal_map_entries_released() {
+    super::key_signals::set_signals_per_scope(8);
+    let sc = ServerRuntime::new_root_scope();
+    let cache = sc.signal_map((0..40).map(|k| (k, k * 2)));
+
+    // more keys than a scope holds signals for, the ones
+    // nobody listens to are disposed on the way
+    assert!((0..40).all(|k| cache.get(&k) == Some(k * 2)));
+    assert!(cache.entry_signals() < 40);
+
+    let users = sc.signal_map([(1, "kiwi"), (2, "fig")]);
+    let output = Rc::new(StringStore::new());
+    signal!(sc, clone: output, move || output
+        .push(format!("{:?}", users.get(&1))));
+    assert_eq!(users.get(&2), Some("fig"));
+    assert_eq!(users.entry_signals(), 2);
+
+    users.remove(&1);
+    users.remove(&2);
+    // the reader of key 1 still listens to it
+    assert_eq!(users.entry_signals(), 1);
+    assert_eq!(output.values(), "Some(\"kiwi\"), None");
+}
+
+#[test]
+fn test_signal_map_created_by_func() {
+    let sc = ServerRuntime::new_root_scope();
+
+    // no scope can be added for the entry signals meanwhile
+    let fruits_sig = signal!(sc, move || sc.signal_map([(1, "lime")]));
+
+    let output = Rc::new(StringStore::new());
+    signal!(sc, clone: output, move || output
+        .push(format!("{:?}", fruits_sig.get().get(&1))));
+
+    fruits_sig.get().insert(1, "plum");
+    fruits_sig.get().insert(2, "fig");
+    assert_eq!(
+        output.values(),
+        "Some(\"lime\"), Some(\"plum\"), Some(\"plum\")"
+    );
+}
+
+#[test]
+fn test_signal_selector() {
+    let sc = ServerRuntime::new_root_scope();
+    let selected = signal!(sc, 1);
+    let selector = selected.selector();
+
+    let runs = Rc::new(Cell::new(0));
+    let rows = (0..100)
+        .map(|id| {
+            let r = runs.clone();
+            signal!(sc, move || {
+                r.inc();
+                selector.is(&id)
+            })
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(runs.get(), 100);
+
+    selected.set(2);
+    assert_eq!(runs.get(), 102);
+    assert!(!rows[1].get() && rows[2].get());
+
+    // a value without readers
+    selected.set(200);
+    assert_eq!(runs.get(), 103);
+
+    selected.set(200);
+    sc.batch(|| {
+        selected.set(3);
+        selected.set(4);
+    });
+    assert_eq!(runs.get(), 104);
+    assert!(rows[4].get());
+}
+
+#[test]
+fn test_signal_selector_keys_released() {
+    super::key_signals::set_signals_per_scope(8);
+    let sc = ServerRuntime::new_root_scope();
+    let selected = signal!(sc, 1);
+    let selector = selected.selector();
+
+    // more keys than a scope holds signals for
+    assert!((0..40).filter(|id| selector.is(id)).eq([1]));
+    assert!(selector.key_signals() < 40);
+
+    // a single reader of them all, which can't get a scope
+    // added while it runs, subscribes to every change
+    let runs = Rc::new(Cell::new(0));
+    let r = runs.clone();
+    let count = signal!(sc, move || {
+        r.inc();
+        (0..40).filter(|id| selector.is(id)).count()
+    });
+    assert_eq!(count.get(), 1);
+
+    selected.set(39);
+    assert_eq!(runs.get(), 2);
+    selected.set(40);
+    assert_eq!(count.get(), 0);
+}
+
+#[test]
+fn test_signal_selector_created_by_func() {
+    let sc = ServerRuntime::new_root_scope();
+    let selected = signal!(sc, 1);
+
+    // no scope can be added for the key signals meanwhile
+    let selector_sig = signal!(sc, move || selected.selector());
+    let is_two = signal!(sc, move || selector_sig.get().is(&2));
+
+    selected.set(2);
+    assert!(is_two.get());
+    selected.set(3);
+    assert!(!is_two.get());
+}
+
+#[test]
+fn test_signal_trigger_and_event() {
+    let sc = ServerRuntime::new_root_scope();
+    let refresh = sc.trigger();
+    let saved = sc.event::<String>();
+
+    let runs = Rc::new(Cell::new(0));
+    let r = runs.clone();
+    signal!(sc, move || {
+        refresh.track();
+        r.inc();
+    });
+
+    let output = Rc::new(StringStore::new());
+    let out = output.clone();
+    let lazy_out = output.clone();
+    signal!(sc, move || saved.with(|name| out.push(format!("{name:?}"))));
+    let lazy = signal!(sc, lazy, move || saved.cloned());
+    assert_eq!(runs.get(), 1);
+
+    refresh.notify();
+    refresh.notify();
+    assert_eq!(runs.get(), 3);
+
+    saved.emit("kiwi".to_string());
+    sc.batch(|| {
+        saved.emit("fig".to_string());
+        saved.emit("lime".to_string());
+        refresh.notify();
+    });
+    assert_eq!(runs.get(), 4);
+    assert_eq!(output.values(), r#"None, Some("kiwi"), Some("lime")"#);
+
+    // the payload is dropped once the event is propagated
+    assert_eq!(saved.cloned(), None);
+    lazy_out.push(format!("{:?}", lazy.cloned()));
+    assert_eq!(output.values(), r#"None, Some("kiwi"), Some("lime"), None"#);
+}
+
+#[test]
+fn test_signal_try_accessors() {
+    use crate::SignalError;
+
+    let root = ServerRuntime::new_root_scope();
+    let sc = root.new_child();
+    let num_sig = signal!(sc, 5);
+    let double = signal!(sc, move || num_sig.get() * 2);
+
+    assert_eq!(num_sig.try_set(6), Ok(()));
+    assert_eq!(num_sig.try_update(|n| *n + 1), Ok(7));
+    assert_eq!(double.try_get(), Ok(12));
+    assert_eq!(double.try_with(|d| d + 1), Ok(13));
+
+    #[cfg(not(feature = "unsafe-cell"))]
+    num_sig.update(|n| {
+        assert_eq!(num_sig.try_get(), Err(SignalError::BorrowConflict));
+        assert_eq!(num_sig.try_set(0), Err(SignalError::BorrowConflict));
+        *n += 1;
+    });
+
+    // a signal of another thread
+    let res = std::thread::spawn(move || num_sig.try_get())
+        .join()
+        .unwrap();
+    assert_eq!(res, Err(SignalError::WrongRuntime));
+
+    // read by a signal of another runtime, which can't subscribe to it
+    let other = ServerRuntime::new_root_scope();
+    let foreign = signal!(other, move || num_sig.try_get());
+    assert_eq!(foreign.get(), Err(SignalError::WrongRuntime));
+    assert!(other.untrack(|| num_sig.try_get()).is_ok());
+    other.discard();
+
+    sc.discard();
+    assert_eq!(num_sig.try_get(), Err(SignalError::ScopeDiscarded));
+    assert_eq!(double.try_with(|d| *d), Err(SignalError::ScopeDiscarded));
+
+    let root_sig = signal!(root, 1);
+    root.discard();
+    assert_eq!(root_sig.try_set(2), Err(SignalError::RuntimeDiscarded));
+
+    // the runtime is reused by a new root scope
+    let _root = ServerRuntime::new_root_scope();
+    assert_eq!(root_sig.try_get(), Err(SignalError::RuntimeDiscarded));
+    assert_eq!(num_sig.try_get(), Err(SignalError::RuntimeDiscarded));
+}
+
+#[test]
+fn test_signal_try_accessors_after_panic() {
+    use crate::SignalError;
+    use std::panic::catch_unwind;
+
+    let sc = ServerRuntime::new_root_scope();
+    let child = sc.new_child();
+    let gone_sig = signal!(child, 0);
+    child.discard();
+
+    let num_sig = signal!(sc, 1);
+    // panics by reading a signal of a discarded scope
+    let _sig = signal!(sc, move || {
+        if num_sig.get() == 2 {
+            gone_sig.get();
+        }
+    });
+    let ten_sig = signal!(sc, move || num_sig.get() * 10);
+
+    assert!(catch_unwind(|| num_sig.set(2)).is_err());
+    assert_eq!(gone_sig.try_get(), Err(SignalError::ScopeDiscarded));
+    // the changes are still propagated
+    assert_eq!(num_sig.try_set(3), Ok(()));
+    assert_eq!(ten_sig.try_get(), Ok(30));
+}
+
+#[test]
+fn test_signal_dispose() {
+    use crate::SignalError;
+
+    let sc = ServerRuntime::new_root_scope();
+    let runs = Rc::new(Cell::new(0));
+
+    let num_sig = signal!(sc, 5);
+    let double = signal!(sc, clone: runs, move || {
+        runs.inc();
+        num_sig.get() * 2
+    });
+    let toast = signal!(sc, "Saved");
+
+    toast.dispose();
+    assert!(!toast.is_alive());
+    assert_eq!(toast.try_get(), Err(SignalError::Disposed));
+    // disposing twice does nothing
+    toast.dispose();
+
+    // the slot is reused, but not the handle
+    let reused = signal!(sc, "Deleted");
+    assert_eq!(reused.get(), "Deleted");
+    assert!(reused.is_alive());
+    assert_eq!(toast.try_get(), Err(SignalError::Disposed));
+    assert_eq!(toast.try_set("Sent"), Err(SignalError::Disposed));
+
+    // it no longer listens to num_sig
+    double.dispose();
+    num_sig.set(6);
+    assert_eq!(runs.get(), 1);
+
+    // disposed from an effect, which is running
+    let flag = signal!(sc, false);
+    signal!(sc, effect, move || {
+        if flag.get() {
+            reused.dispose();
+        }
+    });
+    flag.set(true);
+    assert!(!reused.is_alive());
+    assert!(num_sig.is_alive());
+
+    // a slot that has used up its versions isn't reused
+    let mut last = signal!(sc, 1);
+    sc.rt_ref(|rt| rt[last.id].with_signal(last.id, |sig| sig.version.set(u16::MAX - 1)));
+    last.version = u16::MAX - 1;
+    last.dispose();
+    let next = signal!(sc, 2);
+    assert_ne!(next.id, last.id);
+}
+
+#[test]
+fn test_signal_freeze() {
+    use crate::SignalError;
+
+    let sc = ServerRuntime::new_root_scope();
+    let runs = Rc::new(Cell::new(0));
+
+    let num_sig = signal!(sc, 1);
+    let double = signal!(sc, move || num_sig.get() * 2);
+    let lazy = signal!(sc, lazy, move || num_sig.get() * 10);
+    let plus_one = signal!(sc, clone: runs, move || {
+        runs.inc();
+        double.get() + 1
+    });
+
+    double.freeze();
+    lazy.freeze();
+    num_sig.set(2);
+    assert_eq!(double.get(), 2);
+    assert_eq!(lazy.get(), 10);
+    assert_eq!(plus_one.get(), 3);
+    assert_eq!(runs.get(), 1);
+
+    num_sig.freeze();
+    assert_eq!(num_sig.try_set(3), Err(SignalError::Frozen));
+    assert_eq!(num_sig.try_update(|n| *n += 1), Err(SignalError::Frozen));
+    assert_eq!(num_sig.get(), 2);
+}
+
+#[test]
+fn test_signal_invalidate() {
+    let sc = ServerRuntime::new_root_scope();
+    let clock = Rc::new(Cell::new(1));
+
+    let now = signal!(sc, clone: clock, move || clock.get());
+    let lazy_now = signal!(sc, lazy, clone: clock, move || clock.get());
+    let later = signal!(sc, move || now.get() + 1);
+    let total = signal!(sc, move |total: Option<&i32>| total.unwrap_or(&0)
+        + now.get());
+
+    assert_eq!(lazy_now.get(), 1);
+    clock.set(2);
+    now.invalidate();
+    lazy_now.invalidate();
+    assert_eq!(now.get(), 2);
+    assert_eq!(later.get(), 3);
+    assert_eq!(lazy_now.get(), 2);
+    assert_eq!(total.get(), 3);
+
+    // a reducer runs again with its current value
+    total.invalidate();
+    assert_eq!(total.get(), 5);
+}
+
 trait CellIncr {
     fn inc(&self);
 }
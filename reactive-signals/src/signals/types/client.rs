@@ -1,4 +1,6 @@
-use super::{OptReadable, SignalType};
+use std::hash::Hash;
+
+use super::{hash_of, OptReadable, Runnable, SignalType};
 
 /// A client-side function that produces a value that doesn't implement [PartialEq]
 pub struct ClientFunc<T>(pub(crate) T);
@@ -6,6 +8,7 @@ pub struct ClientFunc<T>(pub(crate) T);
 impl<T> OptReadable for ClientFunc<T> {
     const RUN_ON_SERVER: bool = false;
 }
+impl<T> Runnable for ClientFunc<T> {}
 
 impl<T: 'static> SignalType for ClientFunc<T> {
     type Inner = T;
@@ -24,7 +27,10 @@ impl<T: 'static> SignalType for ClientFunc<T> {
 /// A client-side function that produces a value that implements [PartialEq]
 pub struct ClientEqFunc<T>(pub(crate) T);
 
-impl<T> OptReadable for ClientEqFunc<T> {}
+impl<T> OptReadable for ClientEqFunc<T> {
+    const RUN_ON_SERVER: bool = false;
+}
+impl<T> Runnable for ClientEqFunc<T> {}
 
 impl<T: 'static + PartialEq> SignalType for ClientEqFunc<T> {
     type Inner = T;
@@ -43,3 +49,41 @@ impl<T: 'static + PartialEq> SignalType for ClientEqFunc<T> {
         Self(value)
     }
 }
+
+/// A client-side function that produces a value that implements [Hash](std::hash::Hash).
+/// Only the hash of the previous value is compared with the new value, so two
+/// values with the same hash are taken as equal. It is only created when asked
+/// for with `hash:`, as hashing a value costs about as much as comparing it.
+pub struct ClientHashEqFunc<T>(pub(crate) T, pub(crate) u64);
+
+impl<T> OptReadable for ClientHashEqFunc<T> {
+    const RUN_ON_SERVER: bool = false;
+}
+impl<T> Runnable for ClientHashEqFunc<T> {}
+
+impl<T: 'static + Hash> SignalType for ClientHashEqFunc<T> {
+    type Inner = T;
+
+    fn is_eq(&self, other: &Self::Inner) -> bool {
+        self.1 == hash_of(other)
+    }
+
+    fn opt_hash(&self) -> Option<u64> {
+        Some(self.1)
+    }
+
+    fn inner(&self) -> &Self::Inner {
+        &self.0
+    }
+    fn inner_mut(&mut self) -> &mut Self::Inner {
+        &mut self.0
+    }
+    fn new(value: Self::Inner) -> Self {
+        let hash = hash_of(&value);
+        Self(value, hash)
+    }
+
+    fn set(&mut self, value: Self::Inner) {
+        *self = Self::new(value);
+    }
+}
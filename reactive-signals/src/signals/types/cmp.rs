@@ -0,0 +1,53 @@
+use super::{Modifiable, Readable, Runnable, SignalType};
+
+type CmpFn<T> = Box<dyn Fn(&T, &T) -> bool>;
+
+/// Data that is compared using a custom function
+pub struct CmpData<T>(pub(crate) T, pub(crate) CmpFn<T>);
+
+impl<T> Modifiable for CmpData<T> {}
+impl<T> Readable for CmpData<T> {}
+
+impl<T: 'static> SignalType for CmpData<T> {
+    type Inner = T;
+
+    fn is_eq(&self, other: &Self::Inner) -> bool {
+        (self.1)(&self.0, other)
+    }
+
+    fn inner(&self) -> &Self::Inner {
+        &self.0
+    }
+    fn inner_mut(&mut self) -> &mut Self::Inner {
+        &mut self.0
+    }
+    /// Without a comparison function the values are never equal.
+    fn new(value: Self::Inner) -> Self {
+        Self(value, Box::new(|_, _| false))
+    }
+}
+
+/// A function that produces a value that is compared using a custom function
+pub struct CmpFunc<T>(pub(crate) T, pub(crate) CmpFn<T>);
+
+impl<T> Readable for CmpFunc<T> {}
+impl<T> Runnable for CmpFunc<T> {}
+
+impl<T: 'static> SignalType for CmpFunc<T> {
+    type Inner = T;
+
+    fn is_eq(&self, other: &Self::Inner) -> bool {
+        (self.1)(&self.0, other)
+    }
+
+    fn inner(&self) -> &Self::Inner {
+        &self.0
+    }
+    fn inner_mut(&mut self) -> &mut Self::Inner {
+        &mut self.0
+    }
+    /// Without a comparison function the values are never equal.
+    fn new(value: Self::Inner) -> Self {
+        Self(value, Box::new(|_, _| false))
+    }
+}
@@ -1,9 +1,6 @@
-use std::{
-    collections::hash_map::DefaultHasher,
-    hash::{Hash, Hasher},
-};
+use std::hash::Hash;
 
-use super::{Modifiable, Readable, SignalType};
+use super::{hash_of, Modifiable, Readable, SignalType};
 
 /// Data that doesn't implement [PartialEq]
 pub struct Data<T>(pub(crate) T);
@@ -63,9 +60,7 @@ impl<T: 'static + PartialEq + Hash> SignalType for HashEqData<T> {
     }
 
     fn opt_hash(&self) -> Option<u64> {
-        let mut h = DefaultHasher::new();
-        self.0.hash(&mut h);
-        Some(h.finish())
+        Some(hash_of(&self.0))
     }
 
     fn inner(&self) -> &Self::Inner {
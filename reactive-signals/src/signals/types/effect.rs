@@ -0,0 +1,20 @@
+use super::{Runnable, SignalType};
+
+/// A function that is run for its side effects and doesn't produce a value
+pub struct Effect(pub(crate) ());
+
+impl Runnable for Effect {}
+
+impl SignalType for Effect {
+    type Inner = ();
+
+    fn inner(&self) -> &Self::Inner {
+        &self.0
+    }
+    fn inner_mut(&mut self) -> &mut Self::Inner {
+        &mut self.0
+    }
+    fn new(value: Self::Inner) -> Self {
+        Self(value)
+    }
+}
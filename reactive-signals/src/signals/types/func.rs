@@ -1,9 +1,12 @@
-use super::{Readable, SignalType};
+use std::hash::Hash;
+
+use super::{hash_of, Readable, Runnable, SignalType};
 
 /// A function that produces a value that doesn't implement [PartialEq]
 pub struct Func<T>(pub(crate) T);
 
 impl<T> Readable for Func<T> {}
+impl<T> Runnable for Func<T> {}
 
 impl<T: 'static> SignalType for Func<T> {
     type Inner = T;
@@ -23,6 +26,7 @@ impl<T: 'static> SignalType for Func<T> {
 pub struct EqFunc<T>(pub(crate) T);
 
 impl<T> Readable for EqFunc<T> {}
+impl<T> Runnable for EqFunc<T> {}
 
 impl<T: 'static + PartialEq> SignalType for EqFunc<T> {
     type Inner = T;
@@ -41,3 +45,39 @@ impl<T: 'static + PartialEq> SignalType for EqFunc<T> {
         Self(value)
     }
 }
+
+/// A function that produces a value that implements [Hash](std::hash::Hash).
+/// Only the hash of the previous value is compared with the new value, so two
+/// values with the same hash are taken as equal. It is only created when asked
+/// for with `hash:`, as hashing a value costs about as much as comparing it.
+pub struct HashEqFunc<T>(pub(crate) T, pub(crate) u64);
+
+impl<T> Readable for HashEqFunc<T> {}
+impl<T> Runnable for HashEqFunc<T> {}
+
+impl<T: 'static + Hash> SignalType for HashEqFunc<T> {
+    type Inner = T;
+
+    fn is_eq(&self, other: &Self::Inner) -> bool {
+        self.1 == hash_of(other)
+    }
+
+    fn opt_hash(&self) -> Option<u64> {
+        Some(self.1)
+    }
+
+    fn inner(&self) -> &Self::Inner {
+        &self.0
+    }
+    fn inner_mut(&mut self) -> &mut Self::Inner {
+        &mut self.0
+    }
+    fn new(value: Self::Inner) -> Self {
+        let hash = hash_of(&value);
+        Self(value, hash)
+    }
+
+    fn set(&mut self, value: Self::Inner) {
+        *self = Self::new(value);
+    }
+}
@@ -0,0 +1,73 @@
+use super::{Readable, Runnable, SignalType};
+
+/// A function that produces a value that doesn't implement [PartialEq]
+/// and that only runs when the signal is read.
+pub struct LazyFunc<T>(pub(crate) Option<T>);
+
+impl<T> Readable for LazyFunc<T> {}
+impl<T> Runnable for LazyFunc<T> {}
+
+impl<T> Default for LazyFunc<T> {
+    fn default() -> Self {
+        Self(None)
+    }
+}
+
+impl<T: 'static> SignalType for LazyFunc<T> {
+    type Inner = T;
+
+    fn inner(&self) -> &Self::Inner {
+        self.0
+            .as_ref()
+            .expect("BUG: lazy signal read before it ran")
+    }
+    fn inner_mut(&mut self) -> &mut Self::Inner {
+        self.0
+            .as_mut()
+            .expect("BUG: lazy signal read before it ran")
+    }
+    fn new(value: Self::Inner) -> Self {
+        Self(Some(value))
+    }
+    fn set(&mut self, value: Self::Inner) {
+        self.0 = Some(value);
+    }
+}
+
+/// A function that produces a value that implements [PartialEq]
+/// and that only runs when the signal is read.
+pub struct LazyEqFunc<T>(pub(crate) Option<T>);
+
+impl<T> Readable for LazyEqFunc<T> {}
+impl<T> Runnable for LazyEqFunc<T> {}
+
+impl<T> Default for LazyEqFunc<T> {
+    fn default() -> Self {
+        Self(None)
+    }
+}
+
+impl<T: 'static + PartialEq> SignalType for LazyEqFunc<T> {
+    type Inner = T;
+
+    fn is_eq(&self, other: &Self::Inner) -> bool {
+        self.0.as_ref() == Some(other)
+    }
+
+    fn inner(&self) -> &Self::Inner {
+        self.0
+            .as_ref()
+            .expect("BUG: lazy signal read before it ran")
+    }
+    fn inner_mut(&mut self) -> &mut Self::Inner {
+        self.0
+            .as_mut()
+            .expect("BUG: lazy signal read before it ran")
+    }
+    fn new(value: Self::Inner) -> Self {
+        Self(Some(value))
+    }
+    fn set(&mut self, value: Self::Inner) {
+        self.0 = Some(value);
+    }
+}
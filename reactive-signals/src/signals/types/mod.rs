@@ -1,14 +1,25 @@
 //!
 //! Simple zero-cost abstractions that classifies signals based on the values they produce
 //!
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
+
 mod client;
+mod cmp;
 mod data;
+mod effect;
 mod func;
+mod lazy;
 mod server;
 
 pub use client::*;
+pub use cmp::*;
 pub use data::*;
+pub use effect::*;
 pub use func::*;
+pub use lazy::*;
 pub use server::*;
 
 #[doc(hidden)]
@@ -25,6 +36,17 @@ pub trait SignalType: 'static {
     fn inner(&self) -> &Self::Inner;
     fn inner_mut(&mut self) -> &mut Self::Inner;
     fn new(value: Self::Inner) -> Self;
+
+    fn set(&mut self, value: Self::Inner) {
+        *self.inner_mut() = value;
+    }
+}
+
+/// The hash of `value`, used by the signals that compare their values by hash.
+pub(crate) fn hash_of<T: Hash>(value: &T) -> u64 {
+    let mut h = DefaultHasher::new();
+    value.hash(&mut h);
+    h.finish()
 }
 
 #[doc(hidden)]
@@ -33,6 +55,10 @@ pub trait Modifiable {}
 #[doc(hidden)]
 pub trait Readable {}
 
+/// The signals produced by a function, which can be run again.
+#[doc(hidden)]
+pub trait Runnable {}
+
 #[doc(hidden)]
 pub trait OptReadable {
     const RUN_ON_SERVER: bool = true;
@@ -1,4 +1,6 @@
-use super::{OptReadable, SignalType};
+use std::hash::Hash;
+
+use super::{hash_of, OptReadable, Runnable, SignalType};
 
 /// A server-side function that produces a value that doesn't implement [PartialEq]
 pub struct ServerFunc<T>(pub(crate) T);
@@ -6,6 +8,7 @@ pub struct ServerFunc<T>(pub(crate) T);
 impl<T> OptReadable for ServerFunc<T> {
     const RUN_ON_CLIENT: bool = false;
 }
+impl<T> Runnable for ServerFunc<T> {}
 
 impl<T: 'static> SignalType for ServerFunc<T> {
     type Inner = T;
@@ -24,7 +27,10 @@ impl<T: 'static> SignalType for ServerFunc<T> {
 /// A server-side function that produces a value that implements [PartialEq]
 pub struct ServerEqFunc<T>(pub(crate) T);
 
-impl<T> OptReadable for ServerEqFunc<T> {}
+impl<T> OptReadable for ServerEqFunc<T> {
+    const RUN_ON_CLIENT: bool = false;
+}
+impl<T> Runnable for ServerEqFunc<T> {}
 
 impl<T: 'static + PartialEq> SignalType for ServerEqFunc<T> {
     type Inner = T;
@@ -43,3 +49,41 @@ impl<T: 'static + PartialEq> SignalType for ServerEqFunc<T> {
         Self(value)
     }
 }
+
+/// A server-side function that produces a value that implements [Hash](std::hash::Hash).
+/// Only the hash of the previous value is compared with the new value, so two
+/// values with the same hash are taken as equal. It is only created when asked
+/// for with `hash:`, as hashing a value costs about as much as comparing it.
+pub struct ServerHashEqFunc<T>(pub(crate) T, pub(crate) u64);
+
+impl<T> OptReadable for ServerHashEqFunc<T> {
+    const RUN_ON_CLIENT: bool = false;
+}
+impl<T> Runnable for ServerHashEqFunc<T> {}
+
+impl<T: 'static + Hash> SignalType for ServerHashEqFunc<T> {
+    type Inner = T;
+
+    fn is_eq(&self, other: &Self::Inner) -> bool {
+        self.1 == hash_of(other)
+    }
+
+    fn opt_hash(&self) -> Option<u64> {
+        Some(self.1)
+    }
+
+    fn inner(&self) -> &Self::Inner {
+        &self.0
+    }
+    fn inner_mut(&mut self) -> &mut Self::Inner {
+        &mut self.0
+    }
+    fn new(value: Self::Inner) -> Self {
+        let hash = hash_of(&value);
+        Self(value, hash)
+    }
+
+    fn set(&mut self, value: Self::Inner) {
+        *self = Self::new(value);
+    }
+}
@@ -1,17 +1,188 @@
 use crate::{
+    arena_tree::Tree,
     iter::{NodeResolver, VecTreeIter},
+    primitives::OnUnwind,
     runtimes::{Runtime, RuntimeInner},
-    signals::SignalId,
+    signals::{RunMode, SignalId, SignalInner, SignalState},
+    ScopeInner,
 };
 
+/// The number of times the changes made by the running signals can cause new
+/// changes before it is considered a loop.
+const MAX_FLUSH_ROUNDS: usize = 100;
+
+/// Propagates a change of `sig`, or queues it if there is a batch or a
+/// propagation running.
 pub(crate) fn propagate_change<RT: Runtime>(rt: &RuntimeInner<RT>, sig: SignalId<RT>) {
+    if let Some(reader) = rt.running_reader(sig) {
+        // the running signal changed a signal it has read
+        rt.cycle_detected(reader, Some(sig));
+    } else {
+        propagate_changes(rt, &[sig]);
+    }
+}
+
+/// Propagates the changes of `sigs` followed by the changes made by the
+/// signals that ran. When there is a batch or a propagation running, the
+/// changes are queued until it ends.
+pub(crate) fn propagate_changes<RT: Runtime>(rt: &RuntimeInner<RT>, sigs: &[SignalId<RT>]) {
+    if rt.in_batch() || rt.is_busy() {
+        sigs.iter().for_each(|sig| rt.batch_change(*sig));
+    } else {
+        run_wave(rt, sigs);
+        flush_changes(rt);
+    }
+}
+
+/// Propagates the queued changes, in rounds, until no more changes are made.
+/// Each round propagates all the changes of the previous one together.
+pub(crate) fn flush_changes<RT: Runtime>(rt: &RuntimeInner<RT>) {
+    let mut rounds = 0;
+    while !rt.in_batch() && !rt.is_busy() {
+        let changed = rt.take_changes();
+        if changed.is_empty() || rt.is_stopped() {
+            break;
+        }
+        rounds += 1;
+        if rounds > MAX_FLUSH_ROUNDS {
+            rt.loop_detected(&changed);
+            break;
+        }
+        run_wave(rt, &changed);
+    }
+}
+
+/// Propagates the changes of `sigs` in two passes:
+///
+/// 1. Every signal depending on them is marked. The direct listeners as `Dirty`
+///    and the ones further down as `Check`.
+/// 2. The marked signals are brought up to date in the order they were found.
+///    A signal pulls its sources before it runs, so it runs at most once and
+///    never sees a half-updated graph. Lazy signals are skipped, they stay
+///    marked until they are read, and effects run last.
+///
+/// The payloads of the events in `sigs` are dropped once it is done.
+fn run_wave<RT: Runtime>(rt: &RuntimeInner<RT>, sigs: &[SignalId<RT>]) {
     let tree = &rt.scope_tree;
-    let mut iter = VecTreeIter::new(tree, sig);
+    let mut queue = Vec::new();
+    rt.start_wave();
+
+    for sig in sigs {
+        let mut iter = VecTreeIter::new(tree, *sig);
+
+        while let Some(next) = iter.next() {
+            let node = tree.node(next);
+            if node.state() == SignalState::Clean {
+                node.set_state(SignalState::Check);
+                queue.push(next);
+            } else {
+                // it has already been marked together with its children
+                iter.skip_children();
+            }
+        }
+    }
+    sigs.iter()
+        .for_each(|sig| dirty_listeners(tree, &tree.node(*sig)));
+
+    // a signal that panics leaves the propagation as if it had been stopped
+    let guard = OnUnwind::new(|| {
+        abandon(tree, &queue);
+        sigs.iter().for_each(|sig| tree.node(*sig).end_event());
+        rt.end_wave();
+    });
+
+    queue
+        .iter()
+        .filter(|next| tree.node(**next).mode() == RunMode::Eager)
+        .for_each(|next| update_if_necessary(rt, *next));
+
+    // the effects run once all the values they can read are up to date
+    queue
+        .iter()
+        .filter(|next| tree.node(**next).mode() == RunMode::Effect)
+        .for_each(|next| update_if_necessary(rt, *next));
+
+    guard.disarm();
+    if rt.is_stopped() {
+        abandon(tree, &queue);
+    }
+    sigs.iter().for_each(|sig| tree.node(*sig).end_event());
+    rt.end_wave();
+}
+
+/// Abandons the signals of a stopped propagation that were left behind, so
+/// that they are updated by the next change.
+fn abandon<RT: Runtime>(tree: &Tree<ScopeInner<RT>>, queue: &[SignalId<RT>]) {
+    queue
+        .iter()
+        .map(|next| tree.node(*next))
+        .filter(|node| node.mode() != RunMode::Lazy)
+        .for_each(|node| node.set_state(SignalState::Clean));
+}
+
+/// Brings `sig` up to date. A `Check` signal updates its sources first and
+/// is only re-run if one of them changed and marked it as `Dirty`.
+pub(crate) fn update_if_necessary<RT: Runtime>(rt: &RuntimeInner<RT>, sig: SignalId<RT>) {
+    let tree = &rt.scope_tree;
+    let node = tree.node(sig);
+
+    if node.state() == SignalState::Clean || rt.is_stopped() {
+        return;
+    }
+    if rt.is_running(sig) {
+        rt.cycle_detected(sig, None);
+        return;
+    }
+
+    let mut i = 0;
+    while node.state() == SignalState::Check && i < node.sources.len() {
+        update_if_necessary(rt, node.sources.get(i));
+        i += 1;
+    }
+
+    if node.state() == SignalState::Dirty && node.run(rt, sig) {
+        dirty_listeners(tree, &node);
+    }
+    node.set_state(SignalState::Clean);
+}
+
+/// Runs the func signal `sig` again and propagates the change if its value
+/// changed. A lazy signal only runs again when it is read.
+pub(crate) fn rerun<RT: Runtime>(rt: &RuntimeInner<RT>, sig: SignalId<RT>) {
+    if rt.is_running(sig) {
+        rt.cycle_detected(sig, None);
+        return;
+    }
+    let changed = {
+        let node = rt.scope_tree.node(sig);
+        match node.mode() {
+            RunMode::Frozen => false,
+            RunMode::Lazy => {
+                node.set_state(SignalState::Dirty);
+                true
+            }
+            _ => {
+                let changed = node.run(rt, sig);
+                node.set_state(SignalState::Clean);
+                changed
+            }
+        }
+    };
+    if changed {
+        propagate_change(rt, sig);
+    } else {
+        // the function may have changed other signals
+        flush_changes(rt);
+    }
+}
 
-    while let Some(next) = iter.next() {
-        // println!("upd: {next:?}");
-        if !tree.node(next).run(rt, next) {
-            iter.skip_children();
+/// Marks the listeners that are part of an ongoing propagation as `Dirty`.
+/// The others have already been brought up to date.
+fn dirty_listeners<RT: Runtime>(tree: &Tree<ScopeInner<RT>>, signal: &SignalInner<RT>) {
+    for i in 0..signal.listeners.len() {
+        let listener = tree.node(signal.listeners.get(i));
+        if listener.state() != SignalState::Clean {
+            listener.set_state(SignalState::Dirty);
         }
     }
 }
@@ -31,8 +31,8 @@ fn wasm_sizes() {
     // SignalSet: RefCell & Vec
     assert_eq!(mem::size_of::<SignalSet<3, SignalId<ClientRuntime>>>(), 20);
 
-    // SignalInner: SignalValue + SignalSet
-    let size = if cfg!(debug_assertions) { 40 } else { 36 };
+    // SignalInner: SignalValue + 2 SignalSets + SignalState + RunMode
+    let size = if cfg!(debug_assertions) { 64 } else { 60 };
     assert_eq!(mem::size_of::<SignalInner<ClientRuntime>>(), size);
 }
 
@@ -55,8 +55,8 @@ fn wasm_sizes() {
     // SignalSet: UnsafeCell & Vec
     assert_eq!(mem::size_of::<SignalSet<3, SignalId<ClientRuntime>>>(), 16);
 
-    // SignalInner: SignalValue + SignalSet
-    let size = if cfg!(debug_assertions) { 36 } else { 32 };
+    // SignalInner: SignalValue + 2 SignalSets + SignalState + RunMode
+    let size = if cfg!(debug_assertions) { 56 } else { 52 };
     assert_eq!(mem::size_of::<SignalInner<ClientRuntime>>(), size);
 }
 
@@ -0,0 +1,88 @@
+use std::{cell::RefCell, rc::Rc};
+
+use reactive_signals::{
+    runtimes::{ClientRuntime, Runtime},
+    signal,
+    types::{Data, EqData},
+    Scope, Signal,
+};
+use reactive_signals_derive::Scoped;
+
+#[derive(Scoped, Clone, Copy, PartialEq, Debug)]
+#[scoped(eq)]
+struct Counter(u32);
+
+#[derive(Scoped)]
+#[scoped(CounterScope, name = "AppScope")]
+struct UserName(String);
+
+// named as the fields and the generic parameter a wrapper could have
+#[derive(Scoped)]
+struct Inner(u32);
+
+#[derive(Scoped)]
+#[scoped(InnerScope)]
+struct RT(u32);
+
+fn attach<RT: Runtime>(sc: Scope<RT>) -> AppScope<RT> {
+    let sc: CounterScope<RT> = Counter(1).attach_to(sc);
+    UserName("anna".to_string()).attach_to(sc)
+}
+
+#[test]
+fn test_scoped_attach() {
+    let root = ClientRuntime::new_root_scope();
+    let sc = attach(root.new_child());
+
+    let counter: Signal<EqData<Counter>, ClientRuntime> = sc.counter();
+    let name: Signal<Data<UserName>, ClientRuntime> = sc.user_name();
+
+    assert_eq!(counter.get(), Counter(1));
+    name.with(|n| assert_eq!(n.0, "anna"));
+
+    // the wrappers dereference to the scope they wrap
+    let child = sc.new_child();
+    let doubled = signal!(child, move || sc.counter().with(|c| c.0 * 2));
+    assert_eq!(doubled.get(), 2);
+
+    counter.set(Counter(3));
+    assert_eq!(doubled.get(), 6);
+}
+
+#[test]
+fn test_scoped_signal_macro() {
+    let sc = attach(ClientRuntime::new_root_scope());
+
+    let output = Rc::new(RefCell::new(Vec::new()));
+    signal!(sc, clone: output, move || {
+        let line = sc
+            .user_name()
+            .with(|n| format!("{} {}", n.0, sc.counter().with(|c| c.0)));
+        output.borrow_mut().push(line);
+    });
+
+    sc.user_name().update(|n| n.0 = "bob".to_string());
+    // an equal value doesn't notify
+    sc.counter().set(Counter(1));
+    sc.counter().set(Counter(2));
+
+    assert_eq!(*output.borrow(), ["anna 1", "bob 1", "bob 2"]);
+}
+
+#[test]
+fn test_scoped_discard() {
+    let root = ClientRuntime::new_root_scope();
+    let sc = attach(root.new_child());
+    let counter = sc.counter();
+
+    Scope::from(sc).discard();
+    assert!(!counter.is_alive());
+}
+
+#[test]
+fn test_scoped_colliding_names() {
+    let sc = RT(2).attach_to(Inner(1).attach_to(ClientRuntime::new_root_scope()));
+
+    assert_eq!(sc.inner().with(|i| i.0), 1);
+    assert_eq!(sc.rt().with(|rt| rt.0), 2);
+}